@@ -0,0 +1,154 @@
+//! Parser benchmarks comparing the allocating reference scorer with [`Parser`].
+
+#![expect(missing_docs)]
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+use budouy::model::{CompiledModel, load_default_japanese_parser, parse_model_json};
+use budouy::{Model, Parser};
+
+const CORPORA: [(&str, &str, &str); 4] = [
+    (
+        "ja",
+        include_str!("../src/models/ja.json"),
+        "Google の使命は、世界中の情報を整理し、世界中の人がアクセスできて使えるようにすることです。",
+    ),
+    (
+        "zh-hans",
+        include_str!("../src/models/zh-hans.json"),
+        "我们的使命是整合全球信息，供大众使用，让人人受益。",
+    ),
+    (
+        "zh-hant",
+        include_str!("../src/models/zh-hant.json"),
+        "我們的使命是匯整全球資訊，供大眾使用，使人人受惠。",
+    ),
+    (
+        "th",
+        include_str!("../src/models/th.json"),
+        "พันธกิจของเราคือการจัดระเบียบข้อมูลในโลกนี้และทำให้เข้าถึงได้ง่ายและเป็นประโยชน์",
+    ),
+];
+
+/// The original scorer, which builds a `String` for every n-gram lookup.
+mod legacy {
+    use budouy::Model;
+    use budouy::model::FeatureKey;
+
+    pub struct Parser {
+        model: Model,
+        base_score: f64,
+    }
+
+    impl Parser {
+        pub fn new(model: Model) -> Self {
+            let total: f64 = model
+                .values()
+                .flat_map(|group| group.values())
+                .map(|value| f64::from(*value))
+                .sum();
+            Self {
+                model,
+                base_score: -0.5 * total,
+            }
+        }
+
+        pub fn parse_boundaries(&self, sentence: &str) -> Vec<usize> {
+            let chars: Vec<char> = sentence.chars().collect();
+            let len = chars.len();
+            let mut result = Vec::new();
+            for i in 1..len {
+                let windows = [
+                    (FeatureKey::UW1, i.saturating_sub(3), i.saturating_sub(2)),
+                    (FeatureKey::UW2, i.saturating_sub(2), i.saturating_sub(1)),
+                    (FeatureKey::UW3, i.saturating_sub(1), i),
+                    (FeatureKey::UW4, i, (i + 1).min(len)),
+                    (FeatureKey::UW5, (i + 1).min(len), (i + 2).min(len)),
+                    (FeatureKey::UW6, (i + 2).min(len), (i + 3).min(len)),
+                    (FeatureKey::BW1, i.saturating_sub(2), i),
+                    (FeatureKey::BW2, i.saturating_sub(1), (i + 1).min(len)),
+                    (FeatureKey::BW3, i, (i + 2).min(len)),
+                    (FeatureKey::TW1, i.saturating_sub(3), i),
+                    (FeatureKey::TW2, i.saturating_sub(2), (i + 1).min(len)),
+                    (FeatureKey::TW3, i.saturating_sub(1), (i + 2).min(len)),
+                    (FeatureKey::TW4, i, (i + 3).min(len)),
+                ];
+                let mut score = self.base_score;
+                for (key, start, end) in windows {
+                    let ngram: String = chars[start..end].iter().collect();
+                    score += f64::from(
+                        self.model
+                            .get(&key)
+                            .and_then(|group| group.get(&ngram))
+                            .copied()
+                            .unwrap_or(0),
+                    );
+                }
+                if score > 0.0 {
+                    result.push(i);
+                }
+            }
+            result
+        }
+    }
+}
+
+fn load(json: &str) -> Model {
+    parse_model_json(json).expect("invalid vendored model json")
+}
+
+fn bench_parse_boundaries(c: &mut Criterion) {
+    for (lang, json, sentence) in CORPORA {
+        let model = load(json);
+        let legacy = legacy::Parser::new(model.clone());
+        let parser = Parser::new(model);
+        assert_eq!(
+            legacy.parse_boundaries(sentence),
+            parser.parse_boundaries(sentence),
+            "{lang}: scorers disagree",
+        );
+
+        let mut group = c.benchmark_group(format!("parse_boundaries/{lang}"));
+        group.bench_function("legacy", |b| {
+            b.iter(|| legacy.parse_boundaries(black_box(sentence)));
+        });
+        group.bench_function("parser", |b| {
+            b.iter(|| parser.parse_boundaries(black_box(sentence)));
+        });
+        group.finish();
+    }
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (lang, json, sentence) in CORPORA {
+        let parser = Parser::new(load(json));
+        group.bench_function(lang, |b| b.iter(|| parser.parse(black_box(sentence))));
+    }
+    group.finish();
+}
+
+fn bench_load(c: &mut Criterion) {
+    let (_, json, _) = CORPORA[0];
+    let model = load(json);
+    let compiled = CompiledModel::new(&model);
+    let bytes = compiled.as_bytes().to_vec();
+
+    let mut group = c.benchmark_group("load/ja");
+    group.bench_function("compile", |b| {
+        b.iter(|| Parser::new(black_box(&model).clone()));
+    });
+    group.bench_function("shared", |b| {
+        b.iter(|| Parser::from_compiled(black_box(&compiled).clone()));
+    });
+    group.bench_function("binary", |b| {
+        b.iter(|| CompiledModel::from_bytes(black_box(&bytes).clone()).map(Parser::from_compiled));
+    });
+    group.bench_function("vendored", |b| b.iter(load_default_japanese_parser));
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_boundaries, bench_parse, bench_load);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+//! Precompiles the vendored models into the binary model format, so loading
+//! them at runtime neither parses JSON nor allocates.
+
+extern crate alloc;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::{env, fs};
+
+#[path = "src/model/binary.rs"]
+mod binary;
+
+/// Feature keys, in `FeatureKey::ALL` order.
+const FEATURE_KEYS: [&str; binary::FEATURES] = [
+    "UW1", "UW2", "UW3", "UW4", "UW5", "UW6", "BW1", "BW2", "BW3", "TW1", "TW2", "TW3", "TW4",
+];
+
+/// Vendored model names and the features that enable them.
+const MODELS: [(&str, &str); 4] = [
+    ("ja", "CARGO_FEATURE_MODEL_JA"),
+    ("zh-hans", "CARGO_FEATURE_MODEL_ZH_HANS"),
+    ("zh-hant", "CARGO_FEATURE_MODEL_ZH_HANT"),
+    ("th", "CARGO_FEATURE_MODEL_TH"),
+];
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rerun-if-changed=src/model/binary.rs");
+    let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo");
+    for (name, feature) in MODELS {
+        if env::var_os(feature).is_none() {
+            continue;
+        }
+        let path = format!("src/models/{name}.json");
+        println!("cargo::rerun-if-changed={path}");
+        let json =
+            fs::read_to_string(&path).unwrap_or_else(|err| panic!("failed to read {path}: {err}"));
+        let model: BTreeMap<String, BTreeMap<String, i32>> =
+            serde_json::from_str(&json).unwrap_or_else(|err| panic!("invalid {path}: {err}"));
+        let entries = model.iter().flat_map(|(key, group)| {
+            let feature = FEATURE_KEYS
+                .iter()
+                .position(|known| known == key)
+                .unwrap_or_else(|| panic!("unknown feature key {key} in {path}"));
+            group
+                .iter()
+                .map(move |(ngram, weight)| (feature, ngram.as_str(), *weight))
+        });
+        let output = Path::new(&out_dir).join(format!("{name}.bin"));
+        fs::write(&output, binary::write(entries))
+            .unwrap_or_else(|err| panic!("failed to write {}: {err}", output.display()));
+    }
+}
@@ -0,0 +1,23 @@
+//! Parallel segmentation.
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::Parser;
+
+impl Parser {
+    /// Split each of `inputs` into chunks, in parallel.
+    ///
+    /// Every input is parsed as an independent sentence on the rayon thread
+    /// pool, all sharing this parser's model. The results are in the order of
+    /// `inputs`, and each equals [`parse`](Self::parse) of its input.
+    #[must_use]
+    pub fn parse_batch<S>(&self, inputs: &[S]) -> Vec<Vec<String>>
+    where
+        S: AsRef<str> + Sync,
+    {
+        inputs
+            .par_iter()
+            .map(|input| self.parse(input.as_ref()))
+            .collect()
+    }
+}
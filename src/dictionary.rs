@@ -0,0 +1,241 @@
+//! User dictionaries.
+//!
+//! A [`Dictionary`](crate::dictionary::Dictionary) lists phrases the
+//! statistical model gets wrong, such as brand and product names. It is
+//! applied by [`Parser`](crate::Parser) after scoring, see
+//! [`Parser::with_dictionary`](crate::Parser::with_dictionary).
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::map::HashMap;
+
+/// What a [`Dictionary`] does with a phrase.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Rule {
+    /// Never break inside the phrase.
+    Keep,
+    /// Always break before and after the phrase.
+    Break,
+}
+
+impl Rule {
+    /// Return the name of the rule, as written in dictionary files.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::Break => "break",
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keep" => Ok(Self::Keep),
+            "break" => Ok(Self::Break),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Errors that can occur when loading a dictionary.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum DictionaryError {
+    /// The JSON input is invalid.
+    #[error("invalid dictionary json: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A TSV line names a rule other than `keep` or `break`.
+    #[error("line {line}: unknown rule: {rule}")]
+    UnknownRule {
+        /// One-based line number.
+        line: usize,
+        /// The unrecognized rule.
+        rule: String,
+    },
+    /// A TSV line has a rule but no phrase.
+    #[error("line {line}: empty phrase")]
+    EmptyPhrase {
+        /// One-based line number.
+        line: usize,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DictionaryJson {
+    #[serde(default)]
+    keep: Vec<String>,
+    #[serde(default, rename = "break")]
+    split: Vec<String>,
+}
+
+/// Phrases that are never broken or always broken around.
+///
+/// Phrases are matched exactly, character by character, wherever they occur
+/// in a sentence, including overlapping occurrences. When a forced break
+/// falls inside a [`Rule::Keep`] phrase, the phrase wins.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dictionary {
+    /// Phrases keyed by their first character.
+    phrases: HashMap<char, Vec<(Vec<char>, Rule)>>,
+    len: usize,
+    /// Number of characters in the longest phrase.
+    max_chars: usize,
+}
+
+impl Dictionary {
+    /// Create an empty dictionary.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a phrase, replacing the rule of an existing entry.
+    ///
+    /// Empty phrases are ignored.
+    pub fn insert(&mut self, phrase: &str, rule: Rule) {
+        let phrase: Vec<char> = phrase.chars().collect();
+        let Some(&first) = phrase.first() else {
+            return;
+        };
+        let entries = self.phrases.entry(first).or_default();
+        if let Some(entry) = entries.iter_mut().find(|(existing, _)| *existing == phrase) {
+            entry.1 = rule;
+        } else {
+            self.max_chars = self.max_chars.max(phrase.len());
+            entries.push((phrase, rule));
+            self.len += 1;
+        }
+    }
+
+    /// Return the rule for `phrase`, if it is in the dictionary.
+    #[must_use]
+    pub fn get(&self, phrase: &str) -> Option<Rule> {
+        let first = phrase.chars().next()?;
+        self.phrases
+            .get(&first)?
+            .iter()
+            .find(|(existing, _)| existing.iter().copied().eq(phrase.chars()))
+            .map(|(_, rule)| *rule)
+    }
+
+    /// Return the number of phrases.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether the dictionary has no phrases.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the number of characters in the longest phrase.
+    pub(crate) const fn max_chars(&self) -> usize {
+        self.max_chars
+    }
+
+    /// Parse a dictionary with one phrase per line.
+    ///
+    /// Each line is a phrase, optionally followed by a tab and a rule name,
+    /// `keep` (the default) or `break`. Blank lines and lines starting with
+    /// `#` are skipped. Phrases are taken verbatim, so they may contain
+    /// spaces.
+    ///
+    /// # Errors
+    /// - Returns [`DictionaryError::UnknownRule`] for an unrecognized rule.
+    /// - Returns [`DictionaryError::EmptyPhrase`] for a rule without a phrase.
+    pub fn parse_tsv(input: &str) -> Result<Self, DictionaryError> {
+        let mut dictionary = Self::new();
+        for (index, line) in input.lines().enumerate() {
+            let line_number = index + 1;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (phrase, rule) = match line.split_once('\t') {
+                Some((phrase, rule)) => {
+                    let rule = rule.trim();
+                    let rule = rule.parse().map_err(|()| DictionaryError::UnknownRule {
+                        line: line_number,
+                        rule: rule.into(),
+                    })?;
+                    (phrase, rule)
+                }
+                None => (line, Rule::Keep),
+            };
+            if phrase.is_empty() {
+                return Err(DictionaryError::EmptyPhrase { line: line_number });
+            }
+            dictionary.insert(phrase, rule);
+        }
+        Ok(dictionary)
+    }
+
+    /// Parse a dictionary from JSON such as
+    /// `{"keep": ["Google アシスタント"], "break": ["、"]}`.
+    ///
+    /// Both lists are optional.
+    ///
+    /// # Errors
+    /// Returns [`DictionaryError::Json`] if the input is not valid JSON or has
+    /// other fields.
+    pub fn parse_json(input: &str) -> Result<Self, DictionaryError> {
+        let json: DictionaryJson = serde_json::from_str(input)?;
+        let mut dictionary = Self::new();
+        for phrase in &json.keep {
+            dictionary.insert(phrase, Rule::Keep);
+        }
+        for phrase in &json.split {
+            dictionary.insert(phrase, Rule::Break);
+        }
+        Ok(dictionary)
+    }
+
+    /// Apply the dictionary to sorted `char` boundary indices of `chars`.
+    pub(crate) fn apply(&self, chars: &[char], boundaries: &mut Vec<usize>) {
+        if self.is_empty() {
+            return;
+        }
+        let len = chars.len();
+        let mut kept = vec![false; len + 1];
+        let mut forced = Vec::new();
+        for (start, ch) in chars.iter().enumerate() {
+            let Some(entries) = self.phrases.get(ch) else {
+                continue;
+            };
+            for (phrase, rule) in entries {
+                let end = start + phrase.len();
+                if chars.get(start..end) != Some(phrase.as_slice()) {
+                    continue;
+                }
+                match rule {
+                    Rule::Keep => kept[start + 1..end].fill(true),
+                    Rule::Break => forced.extend([start, end]),
+                }
+            }
+        }
+        boundaries.extend(forced.into_iter().filter(|&i| 0 < i && i < len));
+        boundaries.retain(|&i| !kept[i]);
+        boundaries.sort_unstable();
+        boundaries.dedup();
+    }
+}
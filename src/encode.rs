@@ -0,0 +1,162 @@
+//! Training data encoding.
+//!
+//! Converts segmented sentences into one sample per candidate boundary, in
+//! the upstream `BudouX` encoded format: a `1` or `-1` label followed by the
+//! tab-separated features of that position, e.g. `1\tUW1:今\tUW2:日\t...`.
+//! Windows that are empty near the sentence edges have no feature.
+//! Features are extracted with the same windows [`Parser`](crate::Parser)
+//! scores, so training and inference always see the same n-grams.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use thiserror::Error;
+
+use crate::model::FeatureKey;
+use crate::parser::{FEATURE_WINDOWS, feature_window};
+
+/// Default chunk separator used in segmented corpora.
+pub const DEFAULT_SEPARATOR: char = '\u{2581}';
+
+/// Errors that can occur when reading encoded training data.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum EncodeError {
+    /// A line does not start with a `1` or `-1` label.
+    #[error("line {line}: invalid label {label:?}")]
+    InvalidLabel {
+        /// One-based line number.
+        line: usize,
+        /// The label that was found.
+        label: String,
+    },
+    /// A feature is not of the form `KEY:ngram` with a known key.
+    #[error("line {line}: invalid feature {feature:?}")]
+    InvalidFeature {
+        /// One-based line number.
+        line: usize,
+        /// The feature that was found.
+        feature: String,
+    },
+}
+
+/// Training sample for one candidate boundary.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sample {
+    /// Whether the position is a chunk boundary.
+    pub is_boundary: bool,
+    /// N-gram seen by every feature whose window is not empty, in
+    /// [`FeatureKey::ALL`] order.
+    pub features: Vec<(FeatureKey, String)>,
+}
+
+impl fmt::Display for Sample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.is_boundary { "1" } else { "-1" })?;
+        for (feature, ngram) in &self.features {
+            write!(f, "\t{feature}:{ngram}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Encode a segmented corpus into training samples.
+///
+/// Each line of `corpus` is one sentence whose chunks are separated by
+/// `separator`, e.g. `今日は▁良い▁天気です`. Empty lines are ignored.
+#[must_use]
+pub fn encode(corpus: &str, separator: char) -> Vec<Sample> {
+    corpus
+        .lines()
+        .flat_map(|line| {
+            let (chars, boundaries) = split_sentence(line, separator);
+            encode_chars(&chars, &boundaries)
+        })
+        .collect()
+}
+
+/// Encode one sentence given its boundary `char` indices.
+#[must_use]
+pub fn encode_sentence(sentence: &str, boundaries: &[usize]) -> Vec<Sample> {
+    let chars: Vec<char> = sentence.chars().collect();
+    encode_chars(&chars, boundaries)
+}
+
+/// Parse data in the encoded format back into samples.
+///
+/// Empty lines are ignored.
+///
+/// # Errors
+/// - Returns [`EncodeError::InvalidLabel`] if a line has no valid label.
+/// - Returns [`EncodeError::InvalidFeature`] if a feature is malformed.
+pub fn parse_encoded(input: &str) -> Result<Vec<Sample>, EncodeError> {
+    let mut samples = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split('\t');
+        let is_boundary = match fields.next() {
+            Some("1") => true,
+            Some("-1") => false,
+            label => {
+                return Err(EncodeError::InvalidLabel {
+                    line: index + 1,
+                    label: label.unwrap_or_default().to_string(),
+                });
+            }
+        };
+        let features = fields
+            .map(|field| {
+                field
+                    .split_once(':')
+                    .and_then(|(key, ngram)| Some((key.parse().ok()?, ngram.to_string())))
+                    .ok_or_else(|| EncodeError::InvalidFeature {
+                        line: index + 1,
+                        feature: field.to_string(),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        samples.push(Sample {
+            is_boundary,
+            features,
+        });
+    }
+    Ok(samples)
+}
+
+fn encode_chars(chars: &[char], boundaries: &[usize]) -> Vec<Sample> {
+    let len = chars.len();
+    (1..len)
+        .map(|i| Sample {
+            is_boundary: boundaries.contains(&i),
+            features: FEATURE_WINDOWS
+                .iter()
+                .map(|&(feature, start, end)| (feature, feature_window(i, len, start, end)))
+                .filter(|(_, window)| !window.is_empty())
+                .map(|(feature, window)| (feature, chars[window].iter().collect()))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Split a segmented line into its characters and boundary `char` indices.
+pub(crate) fn split_sentence(line: &str, separator: char) -> (Vec<char>, Vec<usize>) {
+    let mut chars = Vec::new();
+    let mut boundaries = Vec::new();
+    for ch in line.chars() {
+        if ch == separator {
+            if !chars.is_empty() && boundaries.last() != Some(&chars.len()) {
+                boundaries.push(chars.len());
+            }
+        } else {
+            chars.push(ch);
+        }
+    }
+    if boundaries.last() == Some(&chars.len()) {
+        boundaries.pop();
+    }
+    (chars, boundaries)
+}
@@ -0,0 +1,169 @@
+//! Evaluation of a parser against gold-segmented sentences.
+//!
+//! Gold data uses the same format as training corpora: one sentence per line
+//! with chunks separated by a separator character. Every position between two
+//! characters is a candidate boundary, so the metrics count how often the
+//! parser agrees with the gold segmentation on each candidate.
+//! [`compare`](crate::eval::compare) uses the boundaries of a reference parser
+//! as the gold data instead.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::Serialize;
+
+use crate::Parser;
+use crate::encode::split_sentence;
+
+/// Result of [`evaluate`] and [`compare`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Evaluation {
+    /// Metrics over every candidate boundary of the gold data.
+    pub metrics: Metrics,
+    /// Sentences whose predicted boundaries differ from the gold ones.
+    pub diffs: Vec<SentenceDiff>,
+}
+
+/// Boundary classification metrics.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Metrics {
+    /// Number of evaluated sentences.
+    pub sentences: usize,
+    /// Gold boundaries that the parser predicted.
+    pub true_positives: usize,
+    /// Predicted boundaries that are not in the gold data.
+    pub false_positives: usize,
+    /// Gold boundaries that the parser missed.
+    pub false_negatives: usize,
+    /// Positions where neither the gold data nor the parser breaks.
+    pub true_negatives: usize,
+    /// Share of candidate positions classified correctly.
+    pub accuracy: f64,
+    /// Share of predicted boundaries that are gold boundaries.
+    pub precision: f64,
+    /// Share of gold boundaries that were predicted.
+    pub recall: f64,
+    /// Harmonic mean of precision and recall.
+    pub f1: f64,
+}
+
+/// Boundary disagreement on a single sentence.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct SentenceDiff {
+    /// One-based line number in the input.
+    pub line: usize,
+    /// Sentence with the separators removed.
+    pub sentence: String,
+    /// Gold boundaries, as `char` indices.
+    pub gold: Vec<usize>,
+    /// Predicted boundaries, as `char` indices.
+    pub predicted: Vec<usize>,
+    /// Gold boundaries that were not predicted.
+    pub missing: Vec<usize>,
+    /// Predicted boundaries that are not gold boundaries.
+    pub extra: Vec<usize>,
+}
+
+/// Evaluate `parser` against gold-segmented sentences.
+///
+/// Each line of `gold` is one sentence whose chunks are separated by
+/// `separator`, e.g. `今日は▁良い▁天気です`. Empty lines are ignored.
+/// Rates whose denominator is zero are reported as `0.0`.
+#[must_use]
+pub fn evaluate(parser: &Parser, gold: &str, separator: char) -> Evaluation {
+    let mut evaluation = Evaluation::default();
+    for (index, line) in gold.lines().enumerate() {
+        let (chars, gold_boundaries) = split_sentence(line, separator);
+        if chars.is_empty() {
+            continue;
+        }
+        let predicted = parser.parse_boundaries_from_chars(&chars);
+        evaluation.add(index + 1, &chars, gold_boundaries, predicted);
+    }
+    evaluation.metrics.compute_rates();
+    evaluation
+}
+
+/// Measure how closely `parser` agrees with `reference` on unsegmented text.
+///
+/// Each line of `corpus` is one sentence, and the boundaries of `reference`
+/// are used as the gold data, e.g. to check that a pruned model still
+/// segments like the original one. Empty lines are ignored.
+#[must_use]
+pub fn compare(reference: &Parser, parser: &Parser, corpus: &str) -> Evaluation {
+    let mut evaluation = Evaluation::default();
+    for (index, line) in corpus.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        if chars.is_empty() {
+            continue;
+        }
+        let gold = reference.parse_boundaries_from_chars(&chars);
+        let predicted = parser.parse_boundaries_from_chars(&chars);
+        evaluation.add(index + 1, &chars, gold, predicted);
+    }
+    evaluation.metrics.compute_rates();
+    evaluation
+}
+
+impl Evaluation {
+    /// Count the boundaries of one sentence, before the rates are computed.
+    fn add(&mut self, line: usize, chars: &[char], gold: Vec<usize>, predicted: Vec<usize>) {
+        let missing: Vec<usize> = gold
+            .iter()
+            .copied()
+            .filter(|boundary| !predicted.contains(boundary))
+            .collect();
+        let extra: Vec<usize> = predicted
+            .iter()
+            .copied()
+            .filter(|boundary| !gold.contains(boundary))
+            .collect();
+
+        let true_positives = gold.len() - missing.len();
+        let metrics = &mut self.metrics;
+        metrics.sentences += 1;
+        metrics.true_positives += true_positives;
+        metrics.false_positives += extra.len();
+        metrics.false_negatives += missing.len();
+        metrics.true_negatives += chars.len() - 1 - true_positives - extra.len() - missing.len();
+
+        if !missing.is_empty() || !extra.is_empty() {
+            self.diffs.push(SentenceDiff {
+                line,
+                sentence: chars.iter().collect(),
+                gold,
+                predicted,
+                missing,
+                extra,
+            });
+        }
+    }
+}
+
+impl Metrics {
+    fn compute_rates(&mut self) {
+        let predicted = self.true_positives + self.false_positives;
+        let gold = self.true_positives + self.false_negatives;
+        let total = gold + self.false_positives + self.true_negatives;
+        self.accuracy = ratio(self.true_positives + self.true_negatives, total);
+        self.precision = ratio(self.true_positives, predicted);
+        self.recall = ratio(self.true_positives, gold);
+        self.f1 = if self.precision + self.recall > 0.0 {
+            2.0 * self.precision * self.recall / (self.precision + self.recall)
+        } else {
+            0.0
+        };
+    }
+}
+
+#[expect(clippy::cast_precision_loss)]
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
@@ -0,0 +1,83 @@
+//! Per-boundary score explanations.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::Serialize;
+
+use crate::Parser;
+use crate::model::{FeatureKey, pack_chars};
+use crate::parser::{FEATURE_WINDOWS, feature_window};
+
+/// Score breakdown of a single candidate boundary.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BoundaryExplanation {
+    /// Boundary position, as a `char` index into the sentence.
+    pub index: usize,
+    /// Final score: the base score plus every contribution.
+    pub score: f64,
+    /// Base score of the model.
+    pub base_score: f64,
+    /// Whether the parser breaks at this position once its
+    /// [`ParserOptions`](crate::ParserOptions) are applied.
+    pub is_boundary: bool,
+    /// Contribution of every feature, in [`FeatureKey::ALL`] order.
+    pub contributions: Vec<FeatureContribution>,
+}
+
+/// Contribution of one feature to a boundary score.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FeatureContribution {
+    /// Feature key.
+    pub feature: FeatureKey,
+    /// N-gram looked up for this feature (may be empty near sentence ends).
+    pub ngram: String,
+    /// Weight of the n-gram, or `0` when it is empty or the model has no entry
+    /// for it.
+    pub weight: i32,
+}
+
+impl Parser {
+    /// Explain how every candidate boundary of `sentence` is scored.
+    ///
+    /// Returns one entry per position between two characters, whether or not
+    /// the parser breaks there.
+    #[must_use]
+    pub fn explain(&self, sentence: &str) -> Vec<BoundaryExplanation> {
+        let chars: Vec<char> = sentence.chars().collect();
+        let len = chars.len();
+        let model = self.compiled_model();
+        let base_score = model.base_score();
+        let lookup = model.lookup();
+        let boundaries = self.parse_boundaries_from_chars(&chars);
+        (1..len)
+            .map(|index| {
+                let mut score = base_score;
+                let contributions: Vec<FeatureContribution> = FEATURE_WINDOWS
+                    .iter()
+                    .map(|&(feature, start, end)| {
+                        let window = feature_window(index, len, start, end);
+                        let weight = pack_chars(&chars[window.clone()])
+                            .filter(|_| !window.is_empty())
+                            .map_or(0, |code| lookup.weight(feature, code));
+                        score += f64::from(weight);
+                        FeatureContribution {
+                            feature,
+                            ngram: chars[window].iter().collect(),
+                            weight,
+                        }
+                    })
+                    .collect();
+                BoundaryExplanation {
+                    index,
+                    score,
+                    base_score,
+                    is_boundary: boundaries.binary_search(&index).is_ok(),
+                    contributions,
+                }
+            })
+            .collect()
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 
 use crate::Parser;
+use crate::model::load_default_parsers;
 
 use html5ever::{LocalName, QualName};
 #[expect(unused_imports)]
@@ -24,6 +25,14 @@ pub enum Separator {
     Node(NodeRef),
 }
 
+impl Separator {
+    /// Insert an empty element with the given tag name, such as `wbr`.
+    #[must_use]
+    pub fn element(name: &str) -> Self {
+        Self::Node(new_element(name))
+    }
+}
+
 impl Default for Separator {
     fn default() -> Self {
         Self::Text(ZWSP.to_string())
@@ -38,6 +47,13 @@ pub struct HTMLProcessorOptions {
     pub class_name: Option<String>,
     /// Separator to insert at semantic boundaries.
     pub separator: Option<Separator>,
+    /// Language of the parser's model, such as `ja`.
+    ///
+    /// When set, [`HTMLProcessor`] leaves text whose nearest `lang` or
+    /// `xml:lang` attribute names another language as is, and processes text
+    /// without a language. [`MultilingualHTMLProcessor`] uses it for text
+    /// without a language.
+    pub lang: Option<String>,
 }
 
 impl Default for HTMLProcessorOptions {
@@ -45,6 +61,7 @@ impl Default for HTMLProcessorOptions {
         Self {
             class_name: None,
             separator: Some(Separator::default()),
+            lang: None,
         }
     }
 }
@@ -169,12 +186,41 @@ fn action_for_element(node: &NodeRef) -> DomAction {
     }
 }
 
+/// Return the `lang` or `xml:lang` attribute of an element.
+fn element_lang(node: &NodeRef) -> Option<String> {
+    let element = node.as_element()?;
+    let attributes = element.attributes.borrow();
+    attributes
+        .get("lang")
+        .or_else(|| attributes.get("xml:lang"))
+        .map(|lang| lang.trim().to_string())
+}
+
+/// Return the language of `node`, set on itself or its nearest ancestor.
+fn inherited_lang(node: &NodeRef) -> Option<String> {
+    node.inclusive_ancestors()
+        .find_map(|ancestor| element_lang(&ancestor))
+}
+
+/// Return whether the language tag `lang` falls under `range`, so `ja`
+/// matches `ja` and `ja-JP` but not `jav`.
+fn lang_matches(range: &str, lang: &str) -> bool {
+    lang.get(..range.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(range))
+        && matches!(lang.as_bytes().get(range.len()), None | Some(b'-'))
+}
+
 /// HTML processor that applies `BudouX` boundaries to a DOM.
+///
+/// The text of each block is parsed as a whole, so a dictionary set with
+/// [`Parser::with_dictionary`] also matches phrases that span inline elements.
+/// When [`HTMLProcessorOptions::lang`] is set, an element with a `lang`
+/// attribute that differs from its parent's starts a new block.
 #[derive(Debug)]
 pub struct HTMLProcessor {
     parser: Parser,
-    class_name: Option<String>,
-    separator: Separator,
+    splitter: Splitter,
+    lang: Option<String>,
 }
 
 impl HTMLProcessor {
@@ -184,121 +230,272 @@ impl HTMLProcessor {
         let options = options.unwrap_or_default();
         Self {
             parser,
-            class_name: options.class_name,
-            separator: options.separator.unwrap_or_default(),
+            lang: options.lang.clone(),
+            splitter: Splitter::new(options),
         }
     }
 
     /// Apply `BudouX` boundaries to an HTML string.
     #[must_use]
     pub fn apply_to_html_string(&self, html: &str) -> String {
-        if html.is_empty() {
-            return String::new();
-        }
-        let document = kuchikikiki::parse_html()
-            .one(format!("<!doctype html><html><body>{html}</body></html>"));
-        let body = match document.select_first("body") {
-            Ok(body) => body.as_node().clone(),
-            Err(()) => return html.to_string(),
-        };
+        apply_to_fragment(html, |element| self.apply_to_element(element))
+    }
 
-        let children: Vec<NodeRef> = body.children().collect();
-        let child_count = children.len();
-        let has_text_child = children.iter().any(|child| child.as_text().is_some());
+    /// Apply `BudouX` boundaries to a complete HTML document.
+    ///
+    /// Only the content of `<body>` is processed. The doctype, `<head>` and
+    /// the attributes of `<html>` and `<body>` are kept, and the whole
+    /// document is serialized back.
+    ///
+    /// The text before `<html>` and the whitespace after `</html>` are kept
+    /// as is. The rest is serialized from the parsed document, so whitespace
+    /// that HTML parsing drops, such as between `<html>` and `<head>`, is lost,
+    /// and whitespace after `</body>` moves to the end of the body.
+    #[must_use]
+    pub fn apply_to_document(&self, html: &str) -> String {
+        apply_to_body(html, |element| self.apply_to_element(element))
+    }
 
-        let target = if child_count == 1 && !has_text_child {
-            let Some(first_child) = body.first_child() else {
-                return html.to_string();
+    /// Apply `BudouX` boundaries to a DOM element.
+    pub fn apply_to_element(&self, element: &NodeRef) {
+        for block in paragraphs(element, self.lang.is_some()) {
+            let matches = match (&self.lang, &block.lang) {
+                (Some(range), Some(lang)) => lang_matches(range, lang),
+                _ => true,
             };
-            first_child
-        } else {
-            let wrapper = new_element("span");
-            for node in children {
-                node.detach();
-                wrapper.append(node);
+            if matches {
+                self.splitter.apply_to_paragraph(&self.parser, block);
             }
-            body.append(wrapper.clone());
-            wrapper
+        }
+    }
+}
+
+/// HTML processor that picks a [`Parser`] for each block by its language.
+///
+/// The language of a block is its nearest `lang` or `xml:lang` attribute, or
+/// [`HTMLProcessorOptions::lang`] for text without one. It selects the parser
+/// of the first key that matches:
+///
+/// 1. the language tag itself, then with its last subtag removed, so
+///    `ja-JP` falls back to `ja`;
+/// 2. for Chinese (`zh`, `cmn` and `yue`), `zh-hans` or `zh-hant` by script
+///    subtag (`Hans`, `Hant`) or else by region: `TW`, `HK` and `MO`, or
+///    Cantonese, are Traditional and anything else is Simplified.
+///
+/// Keys are compared case-insensitively. Blocks whose language matches no
+/// parser, or that have no language at all, are left as is.
+#[derive(Debug)]
+pub struct MultilingualHTMLProcessor {
+    /// Parsers keyed by lowercase language tag.
+    parsers: HashMap<String, Parser>,
+    default_lang: Option<String>,
+    splitter: Splitter,
+}
+
+impl MultilingualHTMLProcessor {
+    /// Create a processor from parsers keyed by language tag, such as
+    /// `ja` or `zh-hans`.
+    #[must_use]
+    pub fn new<K: AsRef<str>>(
+        parsers: impl IntoIterator<Item = (K, Parser)>,
+        options: Option<HTMLProcessorOptions>,
+    ) -> Self {
+        let options = options.unwrap_or_default();
+        Self {
+            parsers: parsers
+                .into_iter()
+                .map(|(lang, parser)| (lang.as_ref().to_ascii_lowercase(), parser))
+                .collect(),
+            default_lang: options.lang.clone(),
+            splitter: Splitter::new(options),
+        }
+    }
+
+    /// Create a processor with the default parsers compiled into this build,
+    /// see [`load_default_parsers`].
+    #[must_use]
+    pub fn with_default_parsers(options: Option<HTMLProcessorOptions>) -> Self {
+        Self::new(load_default_parsers(), options)
+    }
+
+    /// Return the parser for the language tag `lang`, if there is one.
+    #[must_use]
+    pub fn parser_for(&self, lang: &str) -> Option<&Parser> {
+        let lang = lang.trim().to_ascii_lowercase().replace('_', "-");
+        let mut tag = lang.as_str();
+        loop {
+            if let Some(parser) = self.parsers.get(tag) {
+                return Some(parser);
+            }
+            match tag.rfind('-') {
+                Some(end) => tag = &tag[..end],
+                None => break,
+            }
+        }
+        let mut subtags = lang.split('-');
+        let traditional = match subtags.next() {
+            Some("zh" | "cmn") => subtags
+                .find_map(|subtag| match subtag {
+                    "hant" | "tw" | "hk" | "mo" => Some(true),
+                    "hans" => Some(false),
+                    _ => None,
+                })
+                .unwrap_or(false),
+            Some("yue") => !subtags.any(|subtag| subtag == "hans"),
+            _ => return None,
         };
+        self.parsers
+            .get(if traditional { "zh-hant" } else { "zh-hans" })
+    }
 
-        self.apply_to_element(&target);
+    /// Apply `BudouX` boundaries to an HTML string.
+    #[must_use]
+    pub fn apply_to_html_string(&self, html: &str) -> String {
+        apply_to_fragment(html, |element| self.apply_to_element(element))
+    }
 
-        target.to_string()
+    /// Apply `BudouX` boundaries to a complete HTML document, see
+    /// [`HTMLProcessor::apply_to_document`].
+    #[must_use]
+    pub fn apply_to_document(&self, html: &str) -> String {
+        apply_to_body(html, |element| self.apply_to_element(element))
     }
 
     /// Apply `BudouX` boundaries to a DOM element.
     pub fn apply_to_element(&self, element: &NodeRef) {
-        let mut blocks = Vec::new();
-        self.collect_blocks(element, None, &mut blocks);
-        for block in blocks {
-            if !block.nodes.is_empty() {
-                self.apply_to_paragraph(block);
+        for block in paragraphs(element, true) {
+            let parser = block
+                .lang
+                .as_deref()
+                .or(self.default_lang.as_deref())
+                .and_then(|lang| self.parser_for(lang));
+            if let Some(parser) = parser {
+                self.splitter.apply_to_paragraph(parser, block);
             }
         }
     }
+}
 
-    fn collect_blocks(
-        &self,
-        element: &NodeRef,
-        parent: Option<Paragraph>,
-        output: &mut Vec<Paragraph>,
-    ) {
-        let action = action_for_element(element);
-        if action == DomAction::Skip {
-            return;
-        }
-        if action == DomAction::Break {
-            if let Some(mut parent) = parent
-                && !parent.nodes.is_empty()
-            {
-                parent.set_has_break_opportunity_after();
-                output.push(parent);
-            }
-            return;
-        }
-        if action == DomAction::BreakOpportunity {
-            if let Some(mut parent) = parent {
-                parent.set_has_break_opportunity_after();
-            }
-            return;
+/// Parse `html` as the content of a body and process it with `apply`.
+///
+/// A single element is processed and returned as is, and anything else is
+/// wrapped in a `<span>` first.
+fn apply_to_fragment(html: &str, apply: impl Fn(&NodeRef)) -> String {
+    if html.is_empty() {
+        return String::new();
+    }
+    let document =
+        kuchikikiki::parse_html().one(format!("<!doctype html><html><body>{html}</body></html>"));
+    let body = match document.select_first("body") {
+        Ok(body) => body.as_node().clone(),
+        Err(()) => return html.to_string(),
+    };
+
+    let children: Vec<NodeRef> = body.children().collect();
+    let child_count = children.len();
+    let has_text_child = children.iter().any(|child| child.as_text().is_some());
+
+    let target = if child_count == 1 && !has_text_child {
+        let Some(first_child) = body.first_child() else {
+            return html.to_string();
+        };
+        first_child
+    } else {
+        let wrapper = new_element("span");
+        for node in children {
+            node.detach();
+            wrapper.append(node);
         }
+        body.append(wrapper.clone());
+        wrapper
+    };
 
-        let is_new_block = parent.is_none() || action == DomAction::Block;
-        let mut block = if is_new_block {
-            Paragraph::new(element.clone())
+    apply(&target);
+
+    target.to_string()
+}
+
+/// Parse `html` as a complete document and process its body with `apply`.
+///
+/// The parser drops the whitespace before `<html>` and moves the whitespace
+/// after `</html>` into the body, so the text before the root element and
+/// the whitespace at the end are copied from `html` instead.
+fn apply_to_body(html: &str, apply: impl Fn(&NodeRef)) -> String {
+    let content = html.trim_end_matches(is_html_whitespace);
+    if content.is_empty() {
+        return html.to_string();
+    }
+    let prologue = &html[..prologue_len(html)];
+    // The prologue can reach into the trailing whitespace, e.g. after a lone
+    // doctype.
+    let tail = &html[content.len().max(prologue.len())..];
+    let document = kuchikikiki::parse_html().one(content);
+    if let Ok(body) = document.select_first("body") {
+        apply(body.as_node());
+    }
+    let mut output = String::from(prologue);
+    for node in document
+        .children()
+        .skip_while(|node| node.as_element().is_none())
+    {
+        output.push_str(&node.to_string());
+    }
+    output.push_str(tail);
+    output
+}
+
+/// Length of the byte order mark, doctype, comments and whitespace at the
+/// start of `html`, which the parser puts before the root element.
+fn prologue_len(html: &str) -> usize {
+    let mut rest = html.strip_prefix('\u{FEFF}').unwrap_or(html);
+    loop {
+        rest = rest.trim_start_matches(is_html_whitespace);
+        // `<!...>` and `<?...>` other than comments are doctypes or bogus
+        // comments, which end at the first `>`.
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->").map(|end| end + 3)
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest.find('>').map(|end| end + 1)
         } else {
-            let Some(block) = parent else {
-                return;
-            };
-            block
+            None
         };
-
-        for child in element.children() {
-            match child.data() {
-                NodeData::Element(_) => {
-                    self.collect_blocks(&child, Some(block.clone()), output);
-                }
-                NodeData::Text(_) => {
-                    if action == DomAction::NoBreak {
-                        if let Some(text) = child.as_text() {
-                            block
-                                .nodes
-                                .push(NodeOrText::from_string(text.borrow().clone()));
-                        }
-                    } else {
-                        block.nodes.push(NodeOrText::from_node(child.clone()));
-                    }
-                }
-                _ => {}
-            }
+        match end {
+            Some(end) => rest = &rest[end..],
+            None => return html.len() - rest.len(),
         }
+    }
+}
 
-        if is_new_block && !block.nodes.is_empty() {
-            output.push(block);
+const fn is_html_whitespace(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\x0C' | '\r' | ' ')
+}
+
+/// Return the non-empty paragraphs of `element`, split where the language
+/// changes if `by_lang` is set.
+fn paragraphs(element: &NodeRef, by_lang: bool) -> Vec<Paragraph> {
+    let mut blocks = Vec::new();
+    let lang = element.parent().and_then(|parent| inherited_lang(&parent));
+    collect_blocks(element, None, lang.as_deref(), by_lang, &mut blocks);
+    blocks.retain(|block| !block.nodes.is_empty());
+    blocks
+}
+
+/// Inserts separators at boundaries and styles the processed blocks.
+#[derive(Debug)]
+struct Splitter {
+    class_name: Option<String>,
+    separator: Separator,
+}
+
+impl Splitter {
+    fn new(options: HTMLProcessorOptions) -> Self {
+        Self {
+            class_name: options.class_name,
+            separator: options.separator.unwrap_or_default(),
         }
     }
 
-    fn apply_to_paragraph(&self, mut paragraph: Paragraph) {
+    fn apply_to_paragraph(&self, parser: &Parser, mut paragraph: Paragraph) {
         if !paragraph.nodes.iter().any(NodeOrText::can_split) {
             return;
         }
@@ -306,7 +503,7 @@ impl HTMLProcessor {
         if text.trim().is_empty() {
             return;
         }
-        let boundaries = self.parser.parse_boundaries(&text);
+        let boundaries = parser.parse_boundaries(&text);
         if boundaries.is_empty() {
             return;
         }
@@ -399,6 +596,77 @@ impl HTMLProcessor {
     }
 }
 
+/// Collect the paragraphs of `element`, whose parent has language `inherited`.
+fn collect_blocks(
+    element: &NodeRef,
+    parent: Option<&mut Paragraph>,
+    inherited: Option<&str>,
+    by_lang: bool,
+    output: &mut Vec<Paragraph>,
+) {
+    let action = action_for_element(element);
+    if action == DomAction::Skip {
+        return;
+    }
+    if action == DomAction::Break {
+        if let Some(parent) = parent
+            && !parent.nodes.is_empty()
+        {
+            parent.set_has_break_opportunity_after();
+            output.push(parent.clone());
+            parent.nodes.clear();
+        }
+        return;
+    }
+    if action == DomAction::BreakOpportunity {
+        if let Some(parent) = parent {
+            parent.set_has_break_opportunity_after();
+        }
+        return;
+    }
+
+    // Inline elements add their text to the paragraph of their parent, so
+    // a paragraph spans every inline element inside its block, unless they
+    // change the language and `by_lang` is set.
+    let own_lang = element_lang(element);
+    let lang = own_lang.as_deref().or(inherited);
+    let same_lang = match (lang, inherited) {
+        (Some(lang), Some(inherited)) => lang.eq_ignore_ascii_case(inherited),
+        (lang, inherited) => lang.is_none() && inherited.is_none(),
+    };
+    let mut new_block = None;
+    let block = match parent {
+        Some(parent) if action != DomAction::Block && (same_lang || !by_lang) => parent,
+        _ => new_block.insert(Paragraph::new(element.clone(), lang)),
+    };
+
+    for child in element.children() {
+        match child.data() {
+            NodeData::Element(_) => {
+                collect_blocks(&child, Some(block), lang, by_lang, output);
+            }
+            NodeData::Text(_) => {
+                if action == DomAction::NoBreak {
+                    if let Some(text) = child.as_text() {
+                        block
+                            .nodes
+                            .push(NodeOrText::from_string(text.borrow().clone()));
+                    }
+                } else {
+                    block.nodes.push(NodeOrText::from_node(child.clone()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(block) = new_block
+        && !block.nodes.is_empty()
+    {
+        output.push(block);
+    }
+}
+
 #[derive(Clone)]
 struct NodeOrText {
     node: NodeOrTextInner,
@@ -478,9 +746,9 @@ impl NodeOrText {
                         nodes.push(clone_subtree(sep_node));
                     }
                     nodes.pop();
-                    if let Some(parent) = node.parent() {
-                        for new_node in nodes.iter().rev() {
-                            parent.insert_after(new_node.clone());
+                    if node.parent().is_some() {
+                        for new_node in nodes.into_iter().rev() {
+                            node.insert_after(new_node);
                         }
                         node.detach();
                     }
@@ -494,13 +762,16 @@ impl NodeOrText {
 struct Paragraph {
     element: NodeRef,
     nodes: Vec<NodeOrText>,
+    /// Nearest `lang` of the paragraph's element.
+    lang: Option<String>,
 }
 
 impl Paragraph {
-    const fn new(element: NodeRef) -> Self {
+    fn new(element: NodeRef, lang: Option<&str>) -> Self {
         Self {
             element,
             nodes: Vec::new(),
+            lang: lang.map(str::to_string),
         }
     }
 
@@ -588,6 +859,12 @@ impl HTMLProcessingParser {
     pub fn translate_html_string(&self, html: &str) -> String {
         self.processor.apply_to_html_string(html)
     }
+
+    #[must_use]
+    /// Apply `BudouX` boundaries to a complete HTML document.
+    pub fn translate_html_document(&self, html: &str) -> String {
+        self.processor.apply_to_document(html)
+    }
 }
 
 fn new_element(tag: &str) -> NodeRef {
@@ -7,10 +7,15 @@
 //! # Features
 //! - `std`: Default feature for std-enabled builds.
 //! - `alloc`: `no_std`-compatible build using `alloc` and `hashbrown`.
-//! - `vendored-models`: Bundles default Japanese/Chinese/Thai models.
+//! - `vendored-models`: Bundles default Japanese/Chinese/Thai models, precompiled at build time
+//!   (implies all `model-*` features).
+//! - `model-ja`, `model-zh-hans`, `model-zh-hant`, `model-th`: Bundle a single default model.
 //! - `html`: Enables HTML processing utilities based on `kuchikikiki` (requires `std`).
-//! - `cli`: Enables the `budouy` CLI (requires `std`, implies `vendored-models`).
-//! - `wasm`: Enables WebAssembly bindings via `wasm-bindgen` (implies `alloc` and `vendored-models`).
+//! - `wrap`: Enables width-aware line wrapping at semantic boundaries.
+//! - `train`: Enables `AdaBoost` model training (requires `std`).
+//! - `rayon`: Enables parallel batch parsing with `Parser::parse_batch` (requires `std`).
+//! - `cli`: Enables the `budouy` CLI (requires `std`, implies `wrap`, `train`, `rayon` and `html`).
+//! - `wasm`: Enables WebAssembly bindings via `wasm-bindgen` (implies `alloc`).
 //!
 //! Note: `std` and `alloc` are mutually exclusive.
 //!
@@ -19,7 +24,7 @@
 //! ```toml
 //! budouy = { version = "0.1", default-features = false, features = ["alloc"] }
 //! ```
-//! The `html` and `cli` features require `std`.
+//! The `html`, `train`, `rayon` and `cli` features require `std`.
 //!
 //! # Examples
 //!
@@ -36,17 +41,22 @@
 //! assert_eq!(chunks, vec!["abcde", "abcd"]);
 //! ```
 //!
-//! Use the default Japanese model (requires `vendored-models`):
+//! Use the default Japanese model (requires `model-ja`):
 //! ```rust,no_run
+//! # #[cfg(feature = "model-ja")]
+//! # {
 //! use budouy::model::load_default_japanese_parser;
 //!
 //! let parser = load_default_japanese_parser();
 //! let chunks = parser.parse("今日は良い天気です");
 //! println!("{:?}", chunks);
+//! # }
 //! ```
 //!
-//! Process HTML (requires `html` + `vendored-models`):
+//! Process HTML (requires `html` + `model-ja`):
 //! ```rust,no_run
+//! # #[cfg(all(feature = "html", feature = "model-ja"))]
+//! # {
 //! use budouy::{HTMLProcessingParser, model::load_default_japanese_parser};
 //!
 //! let parser = load_default_japanese_parser();
@@ -54,13 +64,14 @@
 //! let input = "今日は<strong>良い</strong>天気です";
 //! let output = html_parser.translate_html_string(input);
 //! println!("{}", output);
+//! # }
 //! ```
 //!
 //! # WebAssembly
 //!
 //! Build for web with `wasm-pack`:
 //! ```bash
-//! wasm-pack build --target web --no-default-features --features wasm
+//! wasm-pack build --target web --no-default-features --features wasm,vendored-models
 //! ```
 //!
 //! Use from JavaScript:
@@ -92,9 +103,21 @@ pub(crate) mod map {
     pub type HashMap<K, V> = hashbrown::HashMap<K, V>;
 }
 
+/// User dictionaries.
+pub mod dictionary;
+/// Training data encoding.
+pub mod encode;
+/// Evaluation against gold-segmented data.
+pub mod eval;
+mod explain;
 /// Model types and loaders.
 pub mod model;
 mod parser;
+/// Streaming segmentation.
+pub mod stream;
+
+#[cfg(feature = "rayon")]
+mod batch;
 
 #[cfg(feature = "html")]
 mod html_processor;
@@ -102,9 +125,20 @@ mod html_processor;
 #[cfg(feature = "wasm")]
 mod wasm;
 
+/// Model training.
+#[cfg(feature = "train")]
+pub mod train;
+
+/// Width-aware line wrapping.
+#[cfg(feature = "wrap")]
+pub mod wrap;
+
+pub use explain::{BoundaryExplanation, FeatureContribution};
 #[doc(inline)]
 pub use model::Model;
-pub use parser::Parser;
+pub use parser::{Parser, ParserOptions};
 
 #[cfg(feature = "html")]
-pub use html_processor::{HTMLProcessingParser, HTMLProcessor, HTMLProcessorOptions, Separator};
+pub use html_processor::{
+    HTMLProcessingParser, HTMLProcessor, HTMLProcessorOptions, MultilingualHTMLProcessor, Separator,
+};
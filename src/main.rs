@@ -1,14 +1,26 @@
 //! `BudouY` CLI entrypoint.
 
-use std::io::{self, Read};
-use std::{env, fs};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::{env, fs, process};
 
 use seahorse::{App, Command, Context, Flag, FlagType};
+use serde::Serialize;
 
-use budouy::Parser;
+use budouy::dictionary::Dictionary;
+use budouy::encode::{DEFAULT_SEPARATOR, encode, parse_encoded};
+use budouy::eval::{Evaluation, compare, evaluate};
 use budouy::model::{
-    load_default_japanese_parser, load_default_parsers, load_default_simplified_chinese_parser,
-    load_default_thai_parser, load_default_traditional_chinese_parser, parse_model_json,
+    CompiledModel, MergeStrategy, ModelDiff, ModelStats, PruneOptions, Quantization, Severity,
+    diff_models, lint, load_default_parsers, merge_models, model_stats, model_to_json,
+    parse_model_json, prune_model, quantize_model,
+};
+use budouy::train::{TrainOptions, train, train_samples};
+use budouy::wrap::wrap;
+use budouy::{
+    BoundaryExplanation, HTMLProcessor, HTMLProcessorOptions, Model, Parser, ParserOptions,
+    Separator,
 };
 
 fn main() {
@@ -18,9 +30,16 @@ fn main() {
         .description("BudouX parser CLI")
         .usage("budouy <command> [options]")
         .command(parse_command())
+        .command(explain_command())
+        .command(wrap_command())
+        .command(html_command())
+        .command(encode_command())
+        .command(train_command())
+        .command(eval_command())
+        .command(model_command())
         .action(|c| {
             if c.args.is_empty() {
-                eprintln!("No command specified. Use 'budouy parse --help'.");
+                eprintln!("No command specified. Use 'budouy <command> --help'.");
             }
         });
 
@@ -28,78 +47,1373 @@ fn main() {
 }
 
 fn parse_command() -> Command {
-    Command::new("parse")
-        .description("Parse a sentence using a model JSON file or a default model")
-        .usage("budouy parse --model MODEL.json | --lang LANG [--separator SEP] [TEXT]")
-        .flag(Flag::new("model", FlagType::String).description("Path to model JSON"))
-        .flag(Flag::new("lang", FlagType::String).description("Default model language code"))
-        .flag(
-            Flag::new("separator", FlagType::String).description("Chunk separator (default: '|')"),
-        )
-        .action(parse_action)
+    parser_flags(
+        Command::new("parse")
+            .description("Parse a sentence using a model JSON file or a default model")
+            .usage(
+                "budouy parse --model MODEL.json | --lang LANG [--separator SEP] [--lines] [--format text|json|ndjson|tsv|boundaries] [TEXT]",
+            ),
+    )
+    .flag(Flag::new("separator", FlagType::String).description("Chunk separator (default: '|')"))
+    .flag(
+        Flag::new("lines", FlagType::Bool)
+            .description("Parse each line as an independent sentence, in parallel"),
+    )
+    .flag(
+        Flag::new("format", FlagType::String)
+            .description("Output format: text, json, ndjson, tsv or boundaries (default: text)"),
+    )
+    .action(parse_action)
 }
 
-fn parse_action(c: &Context) {
-    let model_path = c.string_flag("model").ok();
-    let lang = c.string_flag("lang").ok();
+const PARSE_FORMATS: [&str; 5] = ["text", "json", "ndjson", "tsv", "boundaries"];
 
-    if model_path.is_some() && lang.is_some() {
-        eprintln!("Specify either --model or --lang, not both.");
+fn parse_action(c: &Context) {
+    let format = c
+        .string_flag("format")
+        .unwrap_or_else(|_| "text".to_string());
+    if !PARSE_FORMATS.contains(&format.as_str()) {
+        eprintln!("Unknown --format value: {format}");
+        eprintln!("Available --format values: {}", PARSE_FORMATS.join(", "));
         return;
     }
-    if model_path.is_none() && lang.is_none() {
-        eprintln!("Missing --model or --lang.");
-        eprintln!("Available --lang values: ja, zh-hans, zh-hant, th");
+    let Some(parser) = load_parser(c) else {
         return;
-    }
-
+    };
     let separator = c
         .string_flag("separator")
         .unwrap_or_else(|_| "|".to_string());
+    let output = ChunkFormat {
+        format: &format,
+        separator: &separator,
+    };
+    // NDJSON has one record per input line.
+    let result = if c.bool_flag("lines") || format == "ndjson" {
+        parse_lines(&parser, output, &c.args)
+    } else if c.args.is_empty() {
+        parse_stdin(&parser, output)
+    } else {
+        let mut stdout = io::stdout().lock();
+        output.write(&mut stdout, 1, parser.parse(&c.args.join(" ")))
+    };
+    if let Err(err) = result {
+        eprintln!("Failed to parse input: {err}");
+    }
+}
 
-    let input = if c.args.is_empty() {
-        read_stdin().unwrap_or_default()
+/// Output format of `budouy parse`.
+#[derive(Clone, Copy)]
+struct ChunkFormat<'a> {
+    format: &'a str,
+    separator: &'a str,
+}
+
+impl<'a> ChunkFormat<'a> {
+    /// Start writing the chunks of the sentence on input line `line`.
+    const fn writer(self, line: usize) -> ChunkWriter<'a> {
+        ChunkWriter {
+            format: self,
+            line,
+            chunks: 0,
+            chars: 0,
+            bytes: 0,
+            records: Vec::new(),
+        }
+    }
+
+    /// Write all chunks of the sentence on input line `line`.
+    fn write<S: AsRef<str>>(
+        self,
+        output: &mut impl Write,
+        line: usize,
+        chunks: impl IntoIterator<Item = S>,
+    ) -> io::Result<()> {
+        let mut writer = self.writer(line);
+        for chunk in chunks {
+            writer.write(output, chunk.as_ref())?;
+        }
+        writer.finish(output)
+    }
+}
+
+/// Writes the chunks of one sentence as they are produced.
+struct ChunkWriter<'a> {
+    format: ChunkFormat<'a>,
+    line: usize,
+    /// Number of chunks written so far.
+    chunks: usize,
+    /// Character offset of the next chunk.
+    chars: usize,
+    /// Byte offset of the next chunk.
+    bytes: usize,
+    /// Chunks of an NDJSON record, written at the end of the sentence.
+    records: Vec<ChunkRecord>,
+}
+
+/// NDJSON record of one input line.
+#[derive(Serialize)]
+struct LineRecord<'a> {
+    line: usize,
+    chunks: &'a [ChunkRecord],
+}
+
+/// A chunk with its character and byte offsets in the line.
+#[derive(Serialize)]
+struct ChunkRecord {
+    text: String,
+    start: usize,
+    end: usize,
+    byte_start: usize,
+    byte_end: usize,
+}
+
+impl ChunkWriter<'_> {
+    fn write(&mut self, output: &mut impl Write, chunk: &str) -> io::Result<()> {
+        let first = self.chunks == 0;
+        let (start, byte_start) = (self.chars, self.bytes);
+        self.chunks += 1;
+        self.chars += chunk.chars().count();
+        self.bytes += chunk.len();
+        match self.format.format {
+            "json" => {
+                output.write_all(if first { b"[" } else { b"," })?;
+                serde_json::to_writer(&mut *output, chunk)?;
+            }
+            "ndjson" => self.records.push(ChunkRecord {
+                text: chunk.to_string(),
+                start,
+                end: self.chars,
+                byte_start,
+                byte_end: self.bytes,
+            }),
+            "tsv" => writeln!(
+                output,
+                "{}\t{start}\t{}\t{}",
+                self.line,
+                self.chars,
+                escape_tsv(chunk)
+            )?,
+            // A boundary precedes every chunk but the first.
+            "boundaries" if !first => {
+                if self.chunks > 2 {
+                    output.write_all(b" ")?;
+                }
+                write!(output, "{start}")?;
+            }
+            "boundaries" => {}
+            _ => {
+                if !first {
+                    output.write_all(self.format.separator.as_bytes())?;
+                }
+                output.write_all(chunk.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self, output: &mut impl Write) -> io::Result<()> {
+        match self.format.format {
+            "json" if self.chunks == 0 => writeln!(output, "[]"),
+            "json" => writeln!(output, "]"),
+            "ndjson" => {
+                let record = LineRecord {
+                    line: self.line,
+                    chunks: &self.records,
+                };
+                serde_json::to_writer(&mut *output, &record)?;
+                writeln!(output)
+            }
+            "tsv" => Ok(()),
+            _ => writeln!(output),
+        }
+    }
+}
+
+/// Escape backslashes, tabs and line breaks so a chunk stays in one TSV field.
+fn escape_tsv(chunk: &str) -> String {
+    let mut escaped = String::with_capacity(chunk.len());
+    for ch in chunk.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Number of stdin lines read and parsed in parallel at a time.
+const BATCH_LINES: usize = 16 * 1024;
+
+/// Parse every line of `args`, or of stdin without arguments, on its own.
+///
+/// Stdin is read in batches of lines, so its size is not limited by memory.
+/// Output lines are in input order.
+fn parse_lines(parser: &Parser, format: ChunkFormat<'_>, args: &[String]) -> io::Result<()> {
+    let mut output = BufWriter::new(io::stdout().lock());
+    if args.is_empty() {
+        let mut input = io::stdin().lock();
+        let mut lines = Vec::with_capacity(BATCH_LINES);
+        let mut line = 1;
+        loop {
+            lines.clear();
+            for text in input.by_ref().lines().take(BATCH_LINES) {
+                lines.push(text?);
+            }
+            if lines.is_empty() {
+                break;
+            }
+            write_batch(&mut output, parser, format, line, &lines)?;
+            line += lines.len();
+        }
     } else {
-        c.args.join(" ")
+        let text = args.join(" ");
+        let lines: Vec<&str> = text.lines().collect();
+        write_batch(&mut output, parser, format, 1, &lines)?;
+    }
+    output.flush()
+}
+
+/// Parse `lines` in parallel, numbering them from `first_line`.
+fn write_batch<S: AsRef<str> + Sync>(
+    output: &mut impl Write,
+    parser: &Parser,
+    format: ChunkFormat<'_>,
+    first_line: usize,
+    lines: &[S],
+) -> io::Result<()> {
+    for (line, chunks) in (first_line..).zip(parser.parse_batch(lines)) {
+        format.write(output, line, chunks)?;
+    }
+    Ok(())
+}
+
+/// Segment stdin as it is read, so large inputs are not held in memory.
+///
+/// Trailing whitespace is held back until more text follows, so the output
+/// matches parsing the whole input with trailing whitespace trimmed.
+fn parse_stdin(parser: &Parser, format: ChunkFormat<'_>) -> io::Result<()> {
+    let mut input = io::stdin().lock();
+    let mut output = BufWriter::new(io::stdout().lock());
+    let mut segmenter = parser.segmenter();
+    let mut writer = format.writer(1);
+    let mut pending = String::new();
+    while input.read_line(&mut pending)? > 0 {
+        let end = pending.trim_end().len();
+        if end > 0 {
+            for chunk in segmenter.push(&pending[..end]) {
+                writer.write(&mut output, &chunk)?;
+            }
+            pending.drain(..end);
+        }
+    }
+    for chunk in segmenter.finish() {
+        writer.write(&mut output, &chunk)?;
+    }
+    writer.finish(&mut output)?;
+    output.flush()
+}
+
+fn explain_command() -> Command {
+    parser_flags(
+        Command::new("explain")
+            .description("Show how every candidate boundary of a sentence is scored")
+            .usage("budouy explain --model MODEL.json | --lang LANG [--format table|json] [TEXT]"),
+    )
+    .flag(
+        Flag::new("format", FlagType::String)
+            .description("Output format: table or json (default: table)"),
+    )
+    .action(explain_action)
+}
+
+fn explain_action(c: &Context) {
+    let format = c
+        .string_flag("format")
+        .unwrap_or_else(|_| "table".to_string());
+    if format != "table" && format != "json" {
+        eprintln!("Unknown --format value: {format}");
+        eprintln!("Available --format values: table, json");
+        return;
+    }
+    let Some(parser) = load_parser(c) else {
+        return;
+    };
+    let input = read_input(c);
+
+    let explanations = parser.explain(&input);
+    if format == "json" {
+        match serde_json::to_string_pretty(&explanations) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("Failed to serialize explanation: {err}"),
+        }
+    } else {
+        print_explanation_table(&input, &explanations);
+    }
+}
+
+fn wrap_command() -> Command {
+    parser_flags(
+        Command::new("wrap")
+            .description("Wrap text to a display width at semantic boundaries")
+            .usage("budouy wrap --model MODEL.json | --lang LANG --width N [TEXT]"),
+    )
+    .flag(
+        Flag::new("width", FlagType::Uint)
+            .description("Maximum line width in columns (default: 80)"),
+    )
+    .action(wrap_action)
+}
+
+fn wrap_action(c: &Context) {
+    let width = c.uint_flag("width").unwrap_or(80);
+    let Some(parser) = load_parser(c) else {
+        return;
+    };
+    let input = read_input(c);
+
+    for line in wrap(&parser, &input, width) {
+        println!("{line}");
+    }
+}
+
+fn html_command() -> Command {
+    parser_flags(
+        Command::new("html")
+            .description("Insert break opportunities into HTML fragments or documents")
+            .usage(
+                "budouy html --model MODEL.json | --lang LANG [--block-lang LANG] [--class-name NAME] [--separator SEP | --separator '<wbr>'] [--in-place | --output-dir DIR] [FILE...]",
+            ),
+    )
+    .flag(
+        Flag::new("block-lang", FlagType::String).description(
+            "Only process text whose nearest lang attribute is LANG or unset (default: --lang)",
+        ),
+    )
+    .flag(
+        Flag::new("class-name", FlagType::String)
+            .description("Class added to processed elements instead of an inline style"),
+    )
+    .flag(
+        Flag::new("separator", FlagType::String)
+            .description("Text inserted at boundaries, or '<wbr>' for an element (default: U+200B)"),
+    )
+    .flag(Flag::new("in-place", FlagType::Bool).description("Rewrite the input files"))
+    .flag(
+        Flag::new("output-dir", FlagType::String)
+            .description("Write each file under DIR, keeping its relative path"),
+    )
+    .action(html_action)
+}
+
+fn html_action(c: &Context) {
+    let in_place = c.bool_flag("in-place");
+    let output_dir = c.string_flag("output-dir").ok();
+    if in_place && output_dir.is_some() {
+        eprintln!("Specify either --in-place or --output-dir, not both.");
+        return;
+    }
+    if (in_place || output_dir.is_some()) && c.args.is_empty() {
+        eprintln!("--in-place and --output-dir need input files.");
+        return;
+    }
+    if let Some(dir) = &output_dir
+        && !check_targets(dir, &c.args)
+    {
+        return;
+    }
+    let Some(parser) = load_parser(c) else {
+        return;
     };
+    let mut options = HTMLProcessorOptions::default();
+    options.class_name = c.string_flag("class-name").ok();
+    options.lang = c
+        .string_flag("block-lang")
+        .or_else(|_| c.string_flag("lang"))
+        .ok();
+    if let Ok(separator) = c.string_flag("separator") {
+        options.separator = Some(if separator.eq_ignore_ascii_case("<wbr>") {
+            Separator::element("wbr")
+        } else {
+            Separator::Text(separator)
+        });
+    }
+    let processor = HTMLProcessor::new(parser, Some(options));
 
-    let parser = if let Some(path) = model_path {
-        let model_json = match fs::read_to_string(path) {
-            Ok(content) => content,
+    if c.args.is_empty() {
+        let mut input = String::new();
+        if let Err(err) = io::stdin().read_to_string(&mut input) {
+            eprintln!("Failed to read stdin: {err}");
+            return;
+        }
+        print!("{}", process_html(&processor, &input));
+        return;
+    }
+    let mut failed = false;
+    for path in &c.args {
+        let input = match fs::read_to_string(path) {
+            Ok(input) => input,
             Err(err) => {
-                eprintln!("Failed to read model file: {err}");
-                return;
+                eprintln!("Failed to read {path}: {err}");
+                failed = true;
+                continue;
             }
         };
-        let model = match parse_model_json(&model_json) {
-            Ok(model) => model,
-            Err(err) => {
-                eprintln!("Failed to parse model: {err}");
-                return;
+        let output = process_html(&processor, &input);
+        let result = match &output_dir {
+            _ if in_place => fs::write(path, output),
+            Some(dir) => write_under(dir, path, &output),
+            None => {
+                print!("{output}");
+                Ok(())
             }
         };
-        Parser::new(model)
-    } else {
-        match lang.as_deref() {
-            Some("ja") => load_default_japanese_parser(),
-            Some("zh-hans") => load_default_simplified_chinese_parser(),
-            Some("zh-hant") => load_default_traditional_chinese_parser(),
-            Some("th") => load_default_thai_parser(),
-            Some(code) => {
-                eprintln!("Unknown --lang value: {code}");
-                eprintln!("Available --lang values: ja, zh-hans, zh-hant, th");
-                return;
+        if let Err(err) = result {
+            eprintln!("Failed to write {path}: {err}");
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Process a whole document, or a fragment when `html` has no doctype or
+/// `<html>` tag after a byte order mark, comments and XML declarations.
+///
+/// Only the body of a document is processed, and everything else is kept.
+/// Whitespace around a fragment is kept as is.
+fn process_html(processor: &HTMLProcessor, html: &str) -> String {
+    let mut start = html.trim_start_matches('\u{FEFF}');
+    loop {
+        start = start.trim_start();
+        let end = if start.starts_with("<!--") {
+            start.find("-->").map(|end| end + 3)
+        } else if start.starts_with("<?") {
+            start.find('>').map(|end| end + 1)
+        } else {
+            None
+        };
+        let Some(end) = end else {
+            break;
+        };
+        start = &start[end..];
+    }
+    let prefix = start.get(..9).unwrap_or(start).to_ascii_lowercase();
+    if prefix.starts_with("<!doctype") || prefix.starts_with("<html") {
+        return processor.apply_to_document(html);
+    }
+    let fragment = html.trim_start();
+    let leading = html.len() - fragment.len();
+    let fragment = fragment.trim_end();
+    format!(
+        "{}{}{}",
+        &html[..leading],
+        processor.apply_to_html_string(fragment),
+        &html[leading + fragment.len()..]
+    )
+}
+
+/// Path under `dir` at the normal components of `path`.
+fn target_under(dir: &str, path: &str) -> PathBuf {
+    let relative: PathBuf = Path::new(path)
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+    Path::new(dir).join(relative)
+}
+
+/// Report input files that would be written to the same path under `dir`,
+/// such as `a/x.html` and `../a/x.html`.
+fn check_targets(dir: &str, paths: &[String]) -> bool {
+    let mut targets: HashMap<PathBuf, &str> = HashMap::new();
+    let mut ok = true;
+    for path in paths {
+        let target = target_under(dir, path);
+        match targets.get(&target) {
+            Some(other) if !same_file(other, path) => {
+                eprintln!(
+                    "{other} and {path} would both be written to {}.",
+                    target.display()
+                );
+                ok = false;
             }
+            Some(_) => {}
             None => {
-                let _ = load_default_parsers();
-                eprintln!("Missing --lang value.");
+                targets.insert(target, path);
+            }
+        }
+    }
+    ok
+}
+
+fn same_file(a: &str, b: &str) -> bool {
+    matches!(
+        (fs::canonicalize(a), fs::canonicalize(b)),
+        (Ok(a), Ok(b)) if a == b
+    )
+}
+
+/// Write `output` under `dir` at the normal components of `path`.
+fn write_under(dir: &str, path: &str, output: &str) -> io::Result<()> {
+    let target = target_under(dir, path);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(target, output)
+}
+
+fn train_command() -> Command {
+    Command::new("train")
+        .description("Train a model from a segmented corpus with AdaBoost")
+        .usage(
+            "budouy train [--iterations N] [--separator SEP | --encoded] [--output MODEL.json] [CORPUS...]",
+        )
+        .flag(
+            Flag::new("iterations", FlagType::Uint)
+                .description("Number of boosting rounds (default: 10000)"),
+        )
+        .flag(
+            Flag::new("separator", FlagType::String)
+                .description("Chunk separator character in the corpus (default: '▁')"),
+        )
+        .flag(
+            Flag::new("output", FlagType::String)
+                .description("Path to write the model JSON (default: stdout)"),
+        )
+        .flag(
+            Flag::new("encoded", FlagType::Bool)
+                .description("Read data produced by 'budouy encode' instead of a corpus"),
+        )
+        .action(train_action)
+}
+
+fn train_action(c: &Context) {
+    let mut options = TrainOptions::default();
+    if let Ok(iterations) = c.uint_flag("iterations") {
+        options.iterations = iterations;
+    }
+    let Some(separator) = separator_flag(c) else {
+        return;
+    };
+    options.separator = separator;
+    let Some(corpus) = read_files_or_stdin(&c.args) else {
+        return;
+    };
+
+    let model = if c.bool_flag("encoded") {
+        match parse_encoded(&corpus) {
+            Ok(samples) => train_samples(samples, Some(options)),
+            Err(err) => {
+                eprintln!("Failed to parse encoded data: {err}");
                 return;
             }
         }
+    } else {
+        train(&corpus, Some(options))
+    };
+    write_model(&model, c.string_flag("output").ok().as_deref());
+}
+
+fn encode_command() -> Command {
+    Command::new("encode")
+        .description("Encode a segmented corpus into BudouX training data")
+        .usage("budouy encode [--separator SEP] [--output DATA.txt] [CORPUS...]")
+        .flag(
+            Flag::new("separator", FlagType::String)
+                .description("Chunk separator character in the corpus (default: '▁')"),
+        )
+        .flag(
+            Flag::new("output", FlagType::String)
+                .description("Path to write the encoded data (default: stdout)"),
+        )
+        .action(encode_action)
+}
+
+fn encode_action(c: &Context) {
+    let Some(separator) = separator_flag(c) else {
+        return;
+    };
+    let Some(corpus) = read_files_or_stdin(&c.args) else {
+        return;
+    };
+
+    let mut output = String::new();
+    for sample in encode(&corpus, separator) {
+        output.push_str(&sample.to_string());
+        output.push('\n');
+    }
+    match c.string_flag("output") {
+        Ok(path) => {
+            if let Err(err) = fs::write(path, output) {
+                eprintln!("Failed to write encoded data: {err}");
+            }
+        }
+        Err(_) => print!("{output}"),
+    }
+}
+
+fn eval_command() -> Command {
+    parser_flags(
+        Command::new("eval")
+            .description("Evaluate a model against gold-segmented sentences")
+            .usage(
+                "budouy eval --model MODEL.json | --lang LANG --data GOLD.txt [--separator SEP] [--format text|json]",
+            ),
+    )
+    .flag(
+        Flag::new("data", FlagType::String)
+            .description("Gold data with one segmented sentence per line (default: stdin)"),
+    )
+    .flag(
+        Flag::new("separator", FlagType::String)
+            .description("Chunk separator character in the gold data (default: '▁')"),
+    )
+    .flag(
+        Flag::new("format", FlagType::String)
+            .description("Output format: text or json (default: text)"),
+    )
+    .action(eval_action)
+}
+
+fn eval_action(c: &Context) {
+    let format = c
+        .string_flag("format")
+        .unwrap_or_else(|_| "text".to_string());
+    if format != "text" && format != "json" {
+        eprintln!("Unknown --format value: {format}");
+        eprintln!("Available --format values: text, json");
+        return;
+    }
+    let Some(separator) = separator_flag(c) else {
+        return;
+    };
+    let Some(parser) = load_parser(c) else {
+        return;
     };
+    let paths: Vec<String> = c.string_flag("data").into_iter().collect();
+    let Some(gold) = read_files_or_stdin(&paths) else {
+        return;
+    };
+
+    let evaluation = evaluate(&parser, &gold, separator);
+    if format == "json" {
+        match serde_json::to_string_pretty(&evaluation) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("Failed to serialize evaluation: {err}"),
+        }
+    } else {
+        print_evaluation(&evaluation);
+    }
+}
+
+fn print_evaluation(evaluation: &Evaluation) {
+    for diff in &evaluation.diffs {
+        println!("line {}: {}", diff.line, diff.sentence);
+        println!("  gold:      {}", segment(&diff.sentence, &diff.gold));
+        println!("  predicted: {}", segment(&diff.sentence, &diff.predicted));
+    }
+    if !evaluation.diffs.is_empty() {
+        println!();
+    }
+    let metrics = &evaluation.metrics;
+    println!("sentences: {}", metrics.sentences);
+    println!(
+        "boundaries: {} tp, {} fp, {} fn, {} tn",
+        metrics.true_positives,
+        metrics.false_positives,
+        metrics.false_negatives,
+        metrics.true_negatives
+    );
+    println!("accuracy:  {:.4}", metrics.accuracy);
+    println!("precision: {:.4}", metrics.precision);
+    println!("recall:    {:.4}", metrics.recall);
+    println!("f1:        {:.4}", metrics.f1);
+}
+
+/// Join the chunks of `sentence` split at `boundaries` with `|`.
+fn segment(sentence: &str, boundaries: &[usize]) -> String {
+    let mut output = String::new();
+    for (index, ch) in sentence.chars().enumerate() {
+        if boundaries.contains(&index) {
+            output.push('|');
+        }
+        output.push(ch);
+    }
+    output
+}
+
+fn model_command() -> Command {
+    Command::new("model")
+        .description("Inspect and transform model files")
+        .usage("budouy model <command> [options]")
+        .command(convert_command())
+        .command(lint_command())
+        .command(merge_command())
+        .command(prune_command())
+        .command(info_command())
+        .command(diff_command())
+        .action(|c| {
+            if c.args.is_empty() {
+                eprintln!("No model command specified. Use 'budouy model <command> --help'.");
+            } else {
+                eprintln!("Unknown model command: {}", c.args[0]);
+            }
+        })
+}
 
-    let chunks = parser.parse(&input);
-    println!("{}", chunks.join(&separator));
+fn convert_command() -> Command {
+    Command::new("convert")
+        .description("Convert a model between the JSON and binary formats")
+        .usage("budouy model convert [--to json|binary] INPUT OUTPUT")
+        .flag(
+            Flag::new("to", FlagType::String)
+                .description("Output format: json or binary (default: the other format)"),
+        )
+        .action(convert_action)
+}
+
+fn convert_action(c: &Context) {
+    let to_binary = match c.string_flag("to").as_deref() {
+        Ok("binary") => Some(true),
+        Ok("json") => Some(false),
+        Ok(format) => {
+            eprintln!("Unknown --to value: {format}");
+            eprintln!("Available --to values: json, binary");
+            return;
+        }
+        Err(_) => None,
+    };
+    let [input, output] = c.args.as_slice() else {
+        eprintln!("Expected an input and an output path.");
+        return;
+    };
+    let Some(bytes) = read_file(input) else {
+        return;
+    };
+    let to_binary = to_binary.unwrap_or_else(|| !CompiledModel::is_binary(&bytes));
+    let Some(model) = load_model(bytes) else {
+        return;
+    };
+
+    if to_binary {
+        if let Err(err) = fs::write(output, model.as_bytes()) {
+            eprintln!("Failed to write model file: {err}");
+        }
+    } else {
+        write_model(&model.to_model(), Some(output));
+    }
+}
+
+fn lint_command() -> Command {
+    Command::new("lint")
+        .description("Report model entries that never match or are likely mistakes")
+        .usage("budouy model lint [--format text|json] MODEL")
+        .flag(
+            Flag::new("format", FlagType::String)
+                .description("Output format: text or json (default: text)"),
+        )
+        .action(lint_action)
+}
+
+fn lint_action(c: &Context) {
+    let format = c
+        .string_flag("format")
+        .unwrap_or_else(|_| "text".to_string());
+    if format != "text" && format != "json" {
+        eprintln!("Unknown --format value: {format}");
+        eprintln!("Available --format values: text, json");
+        return;
+    }
+    let [path] = c.args.as_slice() else {
+        eprintln!("Expected a model path.");
+        return;
+    };
+    let Some(model) = read_file(path).and_then(load_model) else {
+        return;
+    };
+
+    let issues = lint(&model.to_model());
+    if format == "json" {
+        match serde_json::to_string_pretty(&issues) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("Failed to serialize lint issues: {err}"),
+        }
+    } else {
+        for issue in &issues {
+            println!("{issue}");
+        }
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    if format == "text" {
+        println!("{errors} error(s), {} warning(s)", issues.len() - errors);
+    }
+    if errors > 0 {
+        process::exit(1);
+    }
+}
+
+fn merge_command() -> Command {
+    Command::new("merge")
+        .description("Merge a second model into a base model")
+        .usage(
+            "budouy model merge [--strategy sum|override|interpolate] [--weight W] [--output MODEL.json] BASE OTHER",
+        )
+        .flag(
+            Flag::new("strategy", FlagType::String)
+                .description("How to combine weights: sum, override or interpolate (default: override)"),
+        )
+        .flag(
+            Flag::new("weight", FlagType::Float)
+                .description("Share of OTHER when interpolating (default: 0.5)"),
+        )
+        .flag(
+            Flag::new("output", FlagType::String)
+                .description("Path to write the model JSON (default: stdout)"),
+        )
+        .action(merge_action)
+}
+
+fn merge_action(c: &Context) {
+    let strategy = match c.string_flag("strategy").as_deref() {
+        Ok("sum") => MergeStrategy::Sum,
+        Ok("override") | Err(_) => MergeStrategy::Override,
+        Ok("interpolate") => MergeStrategy::Interpolate {
+            weight: c.float_flag("weight").unwrap_or(0.5),
+        },
+        Ok(strategy) => {
+            eprintln!("Unknown --strategy value: {strategy}");
+            eprintln!("Available --strategy values: sum, override, interpolate");
+            return;
+        }
+    };
+    let [base, other] = c.args.as_slice() else {
+        eprintln!("Expected a base and another model path.");
+        return;
+    };
+    let Some(base) = read_file(base).and_then(load_model) else {
+        return;
+    };
+    let Some(other) = read_file(other).and_then(load_model) else {
+        return;
+    };
+
+    let model = merge_models(&base.to_model(), &other.to_model(), strategy);
+    write_model(&model, c.string_flag("output").ok().as_deref());
+}
+
+fn prune_command() -> Command {
+    Command::new("prune")
+        .description("Drop low-magnitude weights and optionally quantize the rest")
+        .usage(
+            "budouy model prune [--min-weight N] [--top-k K] [--quantize i8|i16] [--corpus FILE] [--output MODEL.json] MODEL",
+        )
+        .flag(
+            Flag::new("min-weight", FlagType::Uint)
+                .description("Drop entries whose absolute weight is below N"),
+        )
+        .flag(
+            Flag::new("top-k", FlagType::Uint)
+                .description("Keep the K entries with the largest weights per feature key"),
+        )
+        .flag(
+            Flag::new("quantize", FlagType::String)
+                .description("Scale weights down to fit in i8 or i16"),
+        )
+        .flag(
+            Flag::new("corpus", FlagType::String)
+                .description("Unsegmented sentences to measure agreement with the original model"),
+        )
+        .flag(
+            Flag::new("output", FlagType::String)
+                .description("Path to write the model JSON (default: stdout)"),
+        )
+        .action(prune_action)
+}
+
+fn prune_action(c: &Context) {
+    let quantization = match c.string_flag("quantize").as_deref() {
+        Ok("i8") => Some(Quantization::I8),
+        Ok("i16") => Some(Quantization::I16),
+        Ok(quantization) => {
+            eprintln!("Unknown --quantize value: {quantization}");
+            eprintln!("Available --quantize values: i8, i16");
+            return;
+        }
+        Err(_) => None,
+    };
+    let mut options = PruneOptions::default();
+    if let Ok(min_weight) = c.uint_flag("min-weight") {
+        options.min_weight = Some(u32::try_from(min_weight).unwrap_or(u32::MAX));
+    }
+    options.top_k = c.uint_flag("top-k").ok();
+    let [path] = c.args.as_slice() else {
+        eprintln!("Expected a model path.");
+        return;
+    };
+    let Some(original) = read_file(path).and_then(load_model) else {
+        return;
+    };
+    let corpus = match c.string_flag("corpus") {
+        Ok(corpus) => match read_files_or_stdin(&[corpus]) {
+            Some(corpus) => Some(corpus),
+            None => return,
+        },
+        Err(_) => None,
+    };
+
+    // Count both models the same way: `CompiledModel::len` leaves out the
+    // n-grams longer than three characters, which pruning also sees.
+    let unpruned = original.to_model();
+    let mut model = prune_model(&unpruned, &options);
+    let count = |model: &Model| model.values().map(HashMap::len).sum::<usize>();
+    eprintln!("Entries: {} -> {}", count(&unpruned), count(&model));
+    if let Some(quantization) = quantization {
+        let quantized = quantize_model(&model, quantization);
+        eprintln!("Scale: {}", quantized.scale);
+        model = quantized.model;
+    }
+    if let Some(corpus) = corpus {
+        let reference = Parser::from_compiled(original);
+        let pruned = Parser::new(model.clone());
+        let evaluation = compare(&reference, &pruned, &corpus);
+        let metrics = &evaluation.metrics;
+        eprintln!(
+            "Agreement on {} sentences: accuracy {:.4}, precision {:.4}, recall {:.4}, f1 {:.4}",
+            metrics.sentences, metrics.accuracy, metrics.precision, metrics.recall, metrics.f1
+        );
+        eprintln!(
+            "Sentences segmented differently: {}",
+            evaluation.diffs.len()
+        );
+    }
+    write_model(&model, c.string_flag("output").ok().as_deref());
+}
+
+fn info_command() -> Command {
+    Command::new("info")
+        .description("Show entry counts, weight distributions and script coverage of a model")
+        .usage("budouy model info [--top N] [--format text|json] MODEL")
+        .flag(
+            Flag::new("top", FlagType::Uint)
+                .description("Number of strongest entries of each sign to list (default: 10)"),
+        )
+        .flag(
+            Flag::new("format", FlagType::String)
+                .description("Output format: text or json (default: text)"),
+        )
+        .action(info_action)
+}
+
+fn info_action(c: &Context) {
+    let format = c
+        .string_flag("format")
+        .unwrap_or_else(|_| "text".to_string());
+    if format != "text" && format != "json" {
+        eprintln!("Unknown --format value: {format}");
+        eprintln!("Available --format values: text, json");
+        return;
+    }
+    let [path] = c.args.as_slice() else {
+        eprintln!("Expected a model path.");
+        return;
+    };
+    let Some(model) = read_file(path).and_then(load_model) else {
+        return;
+    };
+
+    let stats = model_stats(&model.to_model(), c.uint_flag("top").unwrap_or(10));
+    if format == "json" {
+        match serde_json::to_string_pretty(&stats) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("Failed to serialize model stats: {err}"),
+        }
+    } else {
+        print_model_stats(&stats);
+    }
+}
+
+fn print_model_stats(stats: &ModelStats) {
+    println!("Entries:    {}", stats.entries);
+    println!("Base score: {}", stats.base_score);
+    let scripts: Vec<String> = stats
+        .scripts
+        .iter()
+        .map(|coverage| format!("{} {}", coverage.script, coverage.characters))
+        .collect();
+    println!("Scripts:    {}", scripts.join(", "));
+    println!();
+    println!(
+        "{:<7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>9}",
+        "FEATURE", "ENTRIES", "MIN", "Q1", "MEDIAN", "Q3", "MAX", "MEAN"
+    );
+    let rows = stats
+        .features
+        .iter()
+        .map(|feature| (feature.feature.as_str(), feature.entries, &feature.weights))
+        .chain([("ALL", stats.entries, &stats.weights)]);
+    for (name, entries, weights) in rows {
+        match weights {
+            Some(weights) => println!(
+                "{name:<7}  {entries:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>9.1}",
+                weights.min, weights.q1, weights.median, weights.q3, weights.max, weights.mean
+            ),
+            None => println!("{name:<7}  {entries:>7}"),
+        }
+    }
+    for (title, entries) in [
+        ("Strongest positive", &stats.strongest_positive),
+        ("Strongest negative", &stats.strongest_negative),
+    ] {
+        println!();
+        println!("{title}:");
+        for entry in entries {
+            println!(
+                "  {:<4} {:>+6}  {:?}",
+                entry.feature, entry.weight, entry.ngram
+            );
+        }
+    }
+}
+
+fn diff_command() -> Command {
+    Command::new("diff")
+        .description("Compare two models and the segmentations they produce")
+        .usage("budouy model diff [--corpus FILE] [--format text|json] OLD NEW")
+        .flag(
+            Flag::new("corpus", FlagType::String)
+                .description("Unsegmented sentences to compare the segmentations on"),
+        )
+        .flag(
+            Flag::new("format", FlagType::String)
+                .description("Output format: text or json (default: text)"),
+        )
+        .action(diff_action)
+}
+
+fn diff_action(c: &Context) {
+    let format = c
+        .string_flag("format")
+        .unwrap_or_else(|_| "text".to_string());
+    if format != "text" && format != "json" {
+        eprintln!("Unknown --format value: {format}");
+        eprintln!("Available --format values: text, json");
+        return;
+    }
+    let [old, new] = c.args.as_slice() else {
+        eprintln!("Expected an old and a new model path.");
+        return;
+    };
+    let Some(old) = read_file(old).and_then(load_model) else {
+        return;
+    };
+    let Some(new) = read_file(new).and_then(load_model) else {
+        return;
+    };
+    let corpus = match c.string_flag("corpus") {
+        Ok(corpus) => match read_files_or_stdin(&[corpus]) {
+            Some(corpus) => Some(corpus),
+            None => return,
+        },
+        Err(_) => None,
+    };
+
+    let diff = diff_models(&old.to_model(), &new.to_model());
+    let segmentation = corpus.map(|corpus| {
+        compare(
+            &Parser::from_compiled(old),
+            &Parser::from_compiled(new),
+            &corpus,
+        )
+    });
+    if format == "json" {
+        let output = serde_json::json!({ "model": diff, "segmentation": segmentation });
+        match serde_json::to_string_pretty(&output) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("Failed to serialize model diff: {err}"),
+        }
+    } else {
+        print_model_diff(&diff);
+        if let Some(segmentation) = &segmentation {
+            print_segmentation_diff(segmentation);
+        }
+    }
+}
+
+fn print_model_diff(diff: &ModelDiff) {
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+    for feature in &diff.features {
+        println!(
+            "{}: {} added, {} removed, {} changed",
+            feature.feature,
+            feature.added.len(),
+            feature.removed.len(),
+            feature.changed.len()
+        );
+        for entry in &feature.added {
+            println!("  + {:>+6}  {:?}", entry.weight, entry.ngram);
+        }
+        for entry in &feature.removed {
+            println!("  - {:>+6}  {:?}", entry.weight, entry.ngram);
+        }
+        for entry in &feature.changed {
+            println!(
+                "  ~ {:>+6} -> {:>+6}  {:?}",
+                entry.old, entry.new, entry.ngram
+            );
+        }
+        added += feature.added.len();
+        removed += feature.removed.len();
+        changed += feature.changed.len();
+    }
+    println!("{added} added, {removed} removed, {changed} changed");
+}
+
+fn print_segmentation_diff(segmentation: &Evaluation) {
+    println!();
+    for diff in &segmentation.diffs {
+        println!("line {}:", diff.line);
+        println!("  - {}", segment(&diff.sentence, &diff.gold));
+        println!("  + {}", segment(&diff.sentence, &diff.predicted));
+    }
+    println!(
+        "Segmentation changed in {} of {} sentences",
+        segmentation.diffs.len(),
+        segmentation.metrics.sentences
+    );
+}
+
+/// Read the single-character `--separator` flag used by corpus commands.
+fn separator_flag(c: &Context) -> Option<char> {
+    let Ok(separator) = c.string_flag("separator") else {
+        return Some(DEFAULT_SEPARATOR);
+    };
+    let mut chars = separator.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        Some(ch)
+    } else {
+        eprintln!("--separator must be a single character.");
+        None
+    }
+}
+
+fn write_model(model: &Model, output: Option<&str>) {
+    let json = match model_to_json(model) {
+        Ok(json) => json,
+        Err(err) => {
+            eprintln!("Failed to serialize model: {err}");
+            return;
+        }
+    };
+    match output {
+        Some(path) => {
+            if let Err(err) = fs::write(path, json) {
+                eprintln!("Failed to write model file: {err}");
+            }
+        }
+        None => println!("{json}"),
+    }
+}
+
+fn print_explanation_table(input: &str, explanations: &[BoundaryExplanation]) {
+    let chars: Vec<char> = input.chars().collect();
+    println!(
+        "{:>5}  {:>10}  {:<5}  {:<8}  FEATURES",
+        "POS", "SCORE", "BREAK", "CONTEXT"
+    );
+    for explanation in explanations {
+        let index = explanation.index;
+        let before: String = chars[index.saturating_sub(3)..index].iter().collect();
+        let after: String = chars[index..(index + 3).min(chars.len())].iter().collect();
+        let features: Vec<String> = explanation
+            .contributions
+            .iter()
+            .filter(|contribution| contribution.weight != 0)
+            .map(|contribution| {
+                format!(
+                    "{}:{}={:+}",
+                    contribution.feature, contribution.ngram, contribution.weight
+                )
+            })
+            .collect();
+        println!(
+            "{index:>5}  {:>10}  {:<5}  {:<8}  {}",
+            explanation.score,
+            if explanation.is_boundary { "yes" } else { "no" },
+            format!("{before}|{after}"),
+            features.join(" "),
+        );
+    }
+}
+
+/// Add the flags read by [`load_parser`] to a command.
+fn parser_flags(command: Command) -> Command {
+    command
+        .flag(Flag::new("model", FlagType::String).description("Path to model JSON"))
+        .flag(Flag::new("lang", FlagType::String).description("Default model language code"))
+        .flag(
+            Flag::new("threshold", FlagType::Float)
+                .description("Score a position must exceed to break (default: 0)"),
+        )
+        .flag(
+            Flag::new("min-chunk", FlagType::Uint)
+                .description("Minimum number of characters in a chunk"),
+        )
+        .flag(
+            Flag::new("max-chunk", FlagType::Uint)
+                .description("Maximum number of characters in a chunk"),
+        )
+        .flag(
+            Flag::new("dict", FlagType::String)
+                .description("Path to a user dictionary (TSV or JSON)"),
+        )
+}
+
+fn load_parser(c: &Context) -> Option<Parser> {
+    let parser = load_model_parser(c)?;
+    let mut options = ParserOptions::default();
+    if let Ok(threshold) = c.float_flag("threshold") {
+        options.threshold = threshold;
+    }
+    if let Ok(min) = c.uint_flag("min-chunk") {
+        options.min_chunk_chars = Some(min);
+    }
+    if let Ok(max) = c.uint_flag("max-chunk") {
+        options.max_chunk_chars = Some(max);
+    }
+    let parser = parser.with_options(options);
+    match c.string_flag("dict") {
+        Ok(path) => Some(parser.with_dictionary(load_dictionary(&path)?)),
+        Err(_) => Some(parser),
+    }
+}
+
+/// Load a user dictionary, in JSON if it is an object and in TSV otherwise.
+fn load_dictionary(path: &str) -> Option<Dictionary> {
+    let input = match fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Failed to read {path}: {err}");
+            return None;
+        }
+    };
+    let dictionary = if input.trim_start().starts_with('{') {
+        Dictionary::parse_json(&input)
+    } else {
+        Dictionary::parse_tsv(&input)
+    };
+    match dictionary {
+        Ok(dictionary) => Some(dictionary),
+        Err(err) => {
+            eprintln!("Failed to parse dictionary {path}: {err}");
+            None
+        }
+    }
+}
+
+fn load_model_parser(c: &Context) -> Option<Parser> {
+    let model_path = c.string_flag("model").ok();
+    let lang = c.string_flag("lang").ok();
+
+    if model_path.is_some() && lang.is_some() {
+        eprintln!("Specify either --model or --lang, not both.");
+        return None;
+    }
+    let Some(path) = model_path else {
+        let mut parsers = load_default_parsers();
+        match lang {
+            Some(code) => match parsers.remove(code.as_str()) {
+                Some(parser) => return Some(parser),
+                None => eprintln!("Unknown --lang value: {code}"),
+            },
+            None if parsers.is_empty() => eprintln!("Missing --model."),
+            None => eprintln!("Missing --model or --lang."),
+        }
+        print_available_languages(parsers.into_keys());
+        return None;
+    };
+    let model = load_model(read_file(&path)?)?;
+    Some(Parser::from_compiled(model))
+}
+
+/// List the `--lang` values supported by this build.
+fn print_available_languages(languages: impl Iterator<Item = &'static str>) {
+    let mut languages: Vec<&str> = languages.collect();
+    if languages.is_empty() {
+        eprintln!("This build has no vendored models for --lang.");
+        return;
+    }
+    languages.sort_unstable();
+    eprintln!("Available --lang values: {}", languages.join(", "));
+}
+
+/// Load a model file in either the JSON or the binary format.
+fn load_model(bytes: Vec<u8>) -> Option<CompiledModel> {
+    let model = if CompiledModel::is_binary(&bytes) {
+        CompiledModel::from_bytes(bytes)
+    } else {
+        let Ok(json) = String::from_utf8(bytes) else {
+            eprintln!("Failed to parse model: model JSON is not valid UTF-8");
+            return None;
+        };
+        parse_model_json(&json).map(CompiledModel::from)
+    };
+    match model {
+        Ok(model) => Some(model),
+        Err(err) => {
+            eprintln!("Failed to parse model: {err}");
+            None
+        }
+    }
+}
+
+fn read_file(path: &str) -> Option<Vec<u8>> {
+    match fs::read(path) {
+        Ok(bytes) => Some(bytes),
+        Err(err) => {
+            eprintln!("Failed to read {path}: {err}");
+            None
+        }
+    }
+}
+
+fn read_input(c: &Context) -> String {
+    if c.args.is_empty() {
+        read_stdin().unwrap_or_default()
+    } else {
+        c.args.join(" ")
+    }
+}
+
+/// Read and concatenate the given files, or stdin when there are none.
+fn read_files_or_stdin(paths: &[String]) -> Option<String> {
+    if paths.is_empty() {
+        let mut input = String::new();
+        if let Err(err) = io::stdin().read_to_string(&mut input) {
+            eprintln!("Failed to read stdin: {err}");
+            return None;
+        }
+        return Some(input);
+    }
+    let mut input = String::new();
+    for path in paths {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                input.push_str(&content);
+                input.push('\n');
+            }
+            Err(err) => {
+                eprintln!("Failed to read {path}: {err}");
+                return None;
+            }
+        }
+    }
+    Some(input)
 }
 
 fn read_stdin() -> io::Result<String> {
@@ -1,13 +1,37 @@
 //! Model types and loaders.
 
+use alloc::collections::BTreeMap;
 use alloc::string::String;
 use core::fmt;
 use core::str::FromStr;
 
+use crate::Parser;
 use crate::map::HashMap;
 
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
+mod binary;
+mod compiled;
+mod diff;
+mod lint;
+mod merge;
+mod prune;
+mod stats;
+
+pub(crate) use binary::pack_chars;
+pub use compiled::CompiledModel;
+pub(crate) use compiled::Lookup;
+pub use diff::{FeatureDiff, ModelDiff, NgramWeight, WeightChange, diff_models};
+pub use lint::{LintIssue, LintKind, Severity, lint};
+pub use merge::{MergeStrategy, merge_models};
+pub use prune::{
+    BASE_SCORE_NGRAM, PruneOptions, Quantization, QuantizedModel, prune_model, quantize_model,
+};
+pub use stats::{
+    FeatureStats, ModelStats, Script, ScriptCoverage, WeightStats, WeightedNgram, model_stats,
+};
+
 /// Feature keys used by the `BudouX` model.
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -41,6 +65,23 @@ pub enum FeatureKey {
 }
 
 impl FeatureKey {
+    /// All feature keys, in the order used by compiled models.
+    pub const ALL: [Self; 13] = [
+        Self::UW1,
+        Self::UW2,
+        Self::UW3,
+        Self::UW4,
+        Self::UW5,
+        Self::UW6,
+        Self::BW1,
+        Self::BW2,
+        Self::BW3,
+        Self::TW1,
+        Self::TW2,
+        Self::TW3,
+        Self::TW4,
+    ];
+
     /// Return the canonical string for this feature key.
     #[must_use]
     pub const fn as_str(self) -> &'static str {
@@ -85,13 +126,19 @@ impl FromStr for FeatureKey {
     }
 }
 
+impl Serialize for FeatureKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 impl fmt::Display for FeatureKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(self.as_str())
     }
 }
 
-/// Errors that can occur when parsing a model JSON string.
+/// Errors that can occur when loading a model.
 #[non_exhaustive]
 #[derive(Debug, Error)]
 pub enum ModelError {
@@ -101,6 +148,12 @@ pub enum ModelError {
     /// A feature key was not recognized.
     #[error("unknown feature key: {0}")]
     UnknownFeature(String),
+    /// The binary model data is malformed.
+    #[error("invalid binary model: {0}")]
+    InvalidBinary(&'static str),
+    /// The binary model was written in an unsupported format version.
+    #[error("unsupported binary model version: {0}")]
+    UnsupportedVersion(u16),
 }
 
 /// `BudouX` model data.
@@ -132,70 +185,104 @@ pub fn parse_model_json(input: &str) -> Result<Model, ModelError> {
     Ok(model)
 }
 
-#[cfg(feature = "vendored-models")]
+/// Serialize a [`Model`] into the `BudouX` model JSON format.
+///
+/// Feature keys and n-grams are written in sorted order, so the output is
+/// stable across runs and can be loaded back with [`parse_model_json`].
+///
+/// # Errors
+/// Returns [`ModelError::Json`] if serialization fails.
+pub fn model_to_json(model: &Model) -> Result<String, ModelError> {
+    let sorted: BTreeMap<&str, BTreeMap<&str, i32>> = model
+        .iter()
+        .map(|(key, group)| {
+            let group = group
+                .iter()
+                .map(|(ngram, weight)| (ngram.as_str(), *weight))
+                .collect();
+            (key.as_str(), group)
+        })
+        .collect();
+    Ok(serde_json::to_string(&sorted)?)
+}
+
+#[cfg(any(
+    feature = "model-ja",
+    feature = "model-zh-hans",
+    feature = "model-zh-hant",
+    feature = "model-th"
+))]
 mod vendored {
-    use super::{Model, parse_model_json};
+    use super::CompiledModel;
     use crate::Parser;
-    use crate::map::HashMap;
-    #[cfg(feature = "std")]
-    use std::sync::LazyLock;
-
-    #[cfg(all(feature = "alloc", not(feature = "std")))]
-    use spin::Lazy as SpinLazy;
-
-    #[cfg(feature = "std")]
-    type Lazy<T> = LazyLock<T>;
-
-    #[cfg(all(feature = "alloc", not(feature = "std")))]
-    type Lazy<T> = SpinLazy<T>;
-
-    static JA_MODEL: Lazy<Model> = Lazy::new(|| {
-        parse_model_json(include_str!("models/ja.json")).expect("invalid ja model json")
-    });
-    static ZH_HANS_MODEL: Lazy<Model> = Lazy::new(|| {
-        parse_model_json(include_str!("models/zh-hans.json")).expect("invalid zh-hans model json")
-    });
-    static ZH_HANT_MODEL: Lazy<Model> = Lazy::new(|| {
-        parse_model_json(include_str!("models/zh-hant.json")).expect("invalid zh-hant model json")
-    });
-    static TH_MODEL: Lazy<Model> = Lazy::new(|| {
-        parse_model_json(include_str!("models/th.json")).expect("invalid th model json")
-    });
+
+    // Written to `OUT_DIR` in the binary model format by the build script and
+    // wrapped at compile time, so loading a default parser does no work.
+    #[cfg(feature = "model-ja")]
+    static JA_MODEL: CompiledModel =
+        CompiledModel::from_precompiled(include_bytes!(concat!(env!("OUT_DIR"), "/ja.bin")));
+    #[cfg(feature = "model-zh-hans")]
+    static ZH_HANS_MODEL: CompiledModel =
+        CompiledModel::from_precompiled(include_bytes!(concat!(env!("OUT_DIR"), "/zh-hans.bin")));
+    #[cfg(feature = "model-zh-hant")]
+    static ZH_HANT_MODEL: CompiledModel =
+        CompiledModel::from_precompiled(include_bytes!(concat!(env!("OUT_DIR"), "/zh-hant.bin")));
+    #[cfg(feature = "model-th")]
+    static TH_MODEL: CompiledModel =
+        CompiledModel::from_precompiled(include_bytes!(concat!(env!("OUT_DIR"), "/th.bin")));
 
     /// Load the default Japanese model parser.
+    #[cfg(feature = "model-ja")]
+    #[must_use]
     pub fn load_default_japanese_parser() -> Parser {
-        Parser::new(JA_MODEL.clone())
+        Parser::from_compiled(JA_MODEL.clone())
     }
 
     /// Load the default Simplified Chinese model parser.
+    #[cfg(feature = "model-zh-hans")]
+    #[must_use]
     pub fn load_default_simplified_chinese_parser() -> Parser {
-        Parser::new(ZH_HANS_MODEL.clone())
+        Parser::from_compiled(ZH_HANS_MODEL.clone())
     }
 
     /// Load the default Traditional Chinese model parser.
+    #[cfg(feature = "model-zh-hant")]
+    #[must_use]
     pub fn load_default_traditional_chinese_parser() -> Parser {
-        Parser::new(ZH_HANT_MODEL.clone())
+        Parser::from_compiled(ZH_HANT_MODEL.clone())
     }
 
     /// Load the default Thai model parser.
-    pub fn load_default_thai_parser() -> Parser {
-        Parser::new(TH_MODEL.clone())
-    }
-
+    #[cfg(feature = "model-th")]
     #[must_use]
-    /// Load all available default parsers keyed by language code.
-    pub fn load_default_parsers() -> HashMap<&'static str, Parser> {
-        HashMap::from([
-            ("ja", load_default_japanese_parser()),
-            ("zh-hans", load_default_simplified_chinese_parser()),
-            ("zh-hant", load_default_traditional_chinese_parser()),
-            ("th", load_default_thai_parser()),
-        ])
+    pub fn load_default_thai_parser() -> Parser {
+        Parser::from_compiled(TH_MODEL.clone())
     }
 }
 
-#[cfg(feature = "vendored-models")]
-pub use vendored::{
-    load_default_japanese_parser, load_default_parsers, load_default_simplified_chinese_parser,
-    load_default_thai_parser, load_default_traditional_chinese_parser,
-};
+#[cfg(feature = "model-ja")]
+pub use vendored::load_default_japanese_parser;
+#[cfg(feature = "model-zh-hans")]
+pub use vendored::load_default_simplified_chinese_parser;
+#[cfg(feature = "model-th")]
+pub use vendored::load_default_thai_parser;
+#[cfg(feature = "model-zh-hant")]
+pub use vendored::load_default_traditional_chinese_parser;
+
+/// Load all default parsers compiled into this build, keyed by language code.
+///
+/// Only languages whose `model-*` feature is enabled are included, so the map
+/// is empty when no vendored model is.
+#[must_use]
+pub fn load_default_parsers() -> HashMap<&'static str, Parser> {
+    HashMap::from([
+        #[cfg(feature = "model-ja")]
+        ("ja", load_default_japanese_parser()),
+        #[cfg(feature = "model-zh-hans")]
+        ("zh-hans", load_default_simplified_chinese_parser()),
+        #[cfg(feature = "model-zh-hant")]
+        ("zh-hant", load_default_traditional_chinese_parser()),
+        #[cfg(feature = "model-th")]
+        ("th", load_default_thai_parser()),
+    ])
+}
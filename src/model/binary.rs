@@ -0,0 +1,106 @@
+//! Writer for the binary model format described on
+//! [`CompiledModel`](super::CompiledModel).
+//!
+//! This module only depends on `core` and `alloc`, so the build script
+//! includes it as well to precompile the vendored models.
+
+use alloc::vec::Vec;
+
+pub const MAGIC: &[u8; 4] = b"BDXM";
+pub const VERSION: u16 = 1;
+/// Number of feature keys, in `FeatureKey::ALL` order.
+pub const FEATURES: usize = 13;
+pub const HEADER_LEN: usize = 32;
+pub const KEYS_START: usize = HEADER_LEN + (FEATURES + 1) * 4;
+/// Size of one string table entry record.
+pub const EXTRA_LEN: usize = 16;
+
+/// Maximum number of characters in an n-gram that can be matched by the parser.
+pub const MAX_NGRAM_CHARS: usize = 3;
+pub const CHAR_BITS: u32 = 21;
+
+/// Serialize `(feature index, n-gram, weight)` entries, given in any order.
+///
+/// # Panics
+/// Panics if the model has more than `u32::MAX` entries.
+pub fn write<'a>(entries: impl IntoIterator<Item = (usize, &'a str, i32)>) -> Vec<u8> {
+    let mut total: i64 = 0;
+    let mut packed: Vec<(usize, u64, i32)> = Vec::new();
+    let mut extras: Vec<(usize, &str, i32)> = Vec::new();
+    for (feature, ngram, weight) in entries {
+        total += i64::from(weight);
+        match pack_str(ngram) {
+            Some(code) => packed.push((feature, code, weight)),
+            None => extras.push((feature, ngram, weight)),
+        }
+    }
+    packed.sort_unstable();
+    extras.sort_unstable();
+
+    let string_len: usize = extras.iter().map(|(_, ngram, _)| ngram.len()).sum();
+    let mut bytes =
+        Vec::with_capacity(KEYS_START + packed.len() * 12 + extras.len() * EXTRA_LEN + string_len);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&0_u16.to_le_bytes());
+    push_u32(&mut bytes, packed.len());
+    push_u32(&mut bytes, extras.len());
+    bytes.extend_from_slice(&total.to_le_bytes());
+    push_u32(&mut bytes, string_len);
+    push_u32(&mut bytes, 0);
+    for feature in 0..=FEATURES {
+        push_u32(
+            &mut bytes,
+            packed.partition_point(|entry| entry.0 < feature),
+        );
+    }
+    for (_, code, _) in &packed {
+        bytes.extend_from_slice(&code.to_le_bytes());
+    }
+    for (_, _, weight) in &packed {
+        bytes.extend_from_slice(&weight.to_le_bytes());
+    }
+    let mut string_offset = 0;
+    for (feature, ngram, weight) in &extras {
+        push_u32(&mut bytes, *feature);
+        push_u32(&mut bytes, string_offset);
+        push_u32(&mut bytes, ngram.len());
+        bytes.extend_from_slice(&weight.to_le_bytes());
+        string_offset += ngram.len();
+    }
+    for (_, ngram, _) in &extras {
+        bytes.extend_from_slice(ngram.as_bytes());
+    }
+    bytes
+}
+
+fn push_u32(bytes: &mut Vec<u8>, value: usize) {
+    let value = u32::try_from(value).expect("model too large for the binary format");
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Pack up to [`MAX_NGRAM_CHARS`] characters into a `u64`.
+///
+/// Each code point is stored off by one so that n-grams of different lengths
+/// never collide; the empty n-gram packs to `0`.
+pub fn pack_chars(chars: &[char]) -> Option<u64> {
+    if chars.len() > MAX_NGRAM_CHARS {
+        return None;
+    }
+    Some(chars.iter().fold(0, |code, ch| {
+        (code << CHAR_BITS) | (u64::from(u32::from(*ch)) + 1)
+    }))
+}
+
+pub fn pack_str(ngram: &str) -> Option<u64> {
+    let mut chars = ['\0'; MAX_NGRAM_CHARS];
+    let mut len = 0;
+    for ch in ngram.chars() {
+        if len == MAX_NGRAM_CHARS {
+            return None;
+        }
+        chars[len] = ch;
+        len += 1;
+    }
+    pack_chars(&chars[..len])
+}
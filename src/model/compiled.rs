@@ -0,0 +1,389 @@
+//! Compiled, read-only model representation and its binary format.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::fmt;
+use core::str;
+
+use super::binary::{
+    self, CHAR_BITS, EXTRA_LEN, FEATURES, HEADER_LEN, KEYS_START, MAGIC, MAX_NGRAM_CHARS, VERSION,
+    pack_str,
+};
+use super::{FeatureKey, InnerModel, Model, ModelError};
+
+const CHAR_MASK: u64 = (1 << CHAR_BITS) - 1;
+
+const _: () = assert!(FEATURES == FeatureKey::ALL.len());
+
+/// Immutable, lookup-optimized form of a [`Model`].
+///
+/// All entries live in a single table sorted by feature key and packed n-gram,
+/// so lookups are a binary search over contiguous memory and never allocate.
+/// Each n-gram of up to three characters is packed into a `u64` with 21 bits
+/// per code point.
+///
+/// The data is either static or reference-counted, so cloning a compiled model
+/// is cheap and never copies its entries.
+///
+/// Entries whose n-gram is longer than three characters can never match a
+/// feature window. They are kept aside in a string table, so they still count
+/// towards [`base_score`](Self::base_score) and are restored by
+/// [`to_model`](Self::to_model).
+///
+/// # Binary format
+/// A compiled model is stored in a compact binary format, returned by
+/// [`as_bytes`](Self::as_bytes). Loading it with [`from_bytes`](Self::from_bytes)
+/// or [`from_static`](Self::from_static) validates the data and then looks up
+/// weights directly in the buffer, so it can be memory-mapped or embedded with
+/// `include_bytes!` without deserializing the model. All integers are
+/// little-endian:
+///
+/// | Offset | Size | Content |
+/// | --- | --- | --- |
+/// | 0 | 4 | Magic `BDXM` |
+/// | 4 | 2 | Format version (`1`) |
+/// | 6 | 2 | Reserved, `0` |
+/// | 8 | 4 | Number of packed entries `n` |
+/// | 12 | 4 | Number of string table entries `m` |
+/// | 16 | 8 | Sum of all weights, as `i64` |
+/// | 24 | 4 | String table length `s` |
+/// | 28 | 4 | Reserved, `0` |
+/// | 32 | 56 | Start of each feature's packed entries, plus `n`, as `u32` |
+/// | 88 | 8n | Packed n-grams, as `u64`, sorted within each feature |
+/// | 88 + 8n | 4n | Weights of the packed n-grams, as `i32` |
+/// | 88 + 12n | 16m | Feature index, string offset, string length (`u32`) and weight (`i32`) of each string table entry |
+/// | 88 + 12n + 16m | s | String table, UTF-8 |
+#[derive(Clone)]
+pub struct CompiledModel {
+    bytes: Bytes,
+    /// Start of each feature's entries in the packed table, plus the end sentinel.
+    offsets: [usize; FeatureKey::ALL.len() + 1],
+    base_score: f64,
+}
+
+#[derive(Clone)]
+enum Bytes {
+    Static(&'static [u8]),
+    Shared(Arc<dyn AsRef<[u8]> + Send + Sync>),
+}
+
+impl Bytes {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Static(bytes) => bytes,
+            Self::Shared(bytes) => (**bytes).as_ref(),
+        }
+    }
+}
+
+impl CompiledModel {
+    /// Compile a [`Model`] into its lookup-optimized form.
+    #[must_use]
+    pub fn new(model: &Model) -> Self {
+        let bytes = binary::write(model.iter().flat_map(|(feature, group)| {
+            group
+                .iter()
+                .map(|(ngram, weight)| (*feature as usize, ngram.as_str(), *weight))
+        }));
+        let (offsets, base_score) = read_header(&bytes);
+        Self {
+            bytes: Bytes::Shared(Arc::new(bytes)),
+            offsets,
+            base_score,
+        }
+    }
+
+    /// Load a model from data in the [binary format](Self#binary-format).
+    ///
+    /// `bytes` is kept as is and used for every lookup, so any owned or
+    /// memory-mapped buffer works, e.g. a `Vec<u8>` or a `memmap2::Mmap`.
+    ///
+    /// # Errors
+    /// - Returns [`ModelError::UnsupportedVersion`] if the data was written by
+    ///   an incompatible version of the format.
+    /// - Returns [`ModelError::InvalidBinary`] if the data is malformed.
+    pub fn from_bytes<B>(bytes: B) -> Result<Self, ModelError>
+    where
+        B: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        validate(bytes.as_ref())?;
+        let (offsets, base_score) = read_header(bytes.as_ref());
+        Ok(Self {
+            bytes: Bytes::Shared(Arc::new(bytes)),
+            offsets,
+            base_score,
+        })
+    }
+
+    /// Load a model from static data in the [binary format](Self#binary-format),
+    /// such as a model embedded with `include_bytes!`.
+    ///
+    /// # Errors
+    /// Same as [`from_bytes`](Self::from_bytes).
+    pub fn from_static(bytes: &'static [u8]) -> Result<Self, ModelError> {
+        validate(bytes)?;
+        Ok(Self::from_precompiled(bytes))
+    }
+
+    /// Wrap static data in the binary format without validating it.
+    ///
+    /// The vendored models are precompiled by the build script and wrapped at
+    /// compile time, so loading them neither parses nor allocates.
+    pub(crate) const fn from_precompiled(bytes: &'static [u8]) -> Self {
+        let (offsets, base_score) = read_header(bytes);
+        Self {
+            bytes: Bytes::Static(bytes),
+            offsets,
+            base_score,
+        }
+    }
+
+    /// Whether `bytes` starts with the [binary format](Self#binary-format) magic.
+    #[must_use]
+    pub fn is_binary(bytes: &[u8]) -> bool {
+        bytes.starts_with(MAGIC)
+    }
+
+    /// Return the model in the [binary format](Self#binary-format).
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Score every boundary starts from: minus half of the sum of all weights.
+    ///
+    /// Since every entry counts towards it, adding, removing or changing any
+    /// entry, e.g. with [`merge_models`](super::merge_models), shifts the
+    /// score of every boundary. [`prune_model`](super::prune_model) keeps it.
+    #[must_use]
+    pub const fn base_score(&self) -> f64 {
+        self.base_score
+    }
+
+    /// Number of entries that can be matched.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.offsets[FeatureKey::ALL.len()]
+    }
+
+    /// Whether the model has no entries that can be matched.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Look up the weight of `ngram` under `feature`, or `0` when absent.
+    #[must_use]
+    pub fn weight(&self, feature: FeatureKey, ngram: &str) -> i32 {
+        pack_str(ngram).map_or(0, |code| self.lookup().weight(feature, code))
+    }
+
+    /// Resolve the packed table once for a series of lookups.
+    pub(crate) fn lookup(&self) -> Lookup<'_> {
+        let bytes = self.bytes.as_slice();
+        let weights_start = self.weights_start();
+        let (keys, _) = bytes[KEYS_START..weights_start].as_chunks();
+        let (weights, _) = bytes[weights_start..weights_start + self.len() * 4].as_chunks();
+        Lookup {
+            offsets: &self.offsets,
+            keys,
+            weights,
+        }
+    }
+
+    /// Expand back into a [`Model`].
+    #[must_use]
+    pub fn to_model(&self) -> Model {
+        let bytes = self.bytes.as_slice();
+        let mut model = Model::with_capacity(FeatureKey::ALL.len());
+        for (index, feature) in FeatureKey::ALL.iter().enumerate() {
+            let range = self.offsets[index]..self.offsets[index + 1];
+            if range.is_empty() {
+                continue;
+            }
+            let group: InnerModel = range
+                .map(|entry| {
+                    let code = u64::from_le_bytes(read(bytes, KEYS_START + entry * 8));
+                    let weight = i32::from_le_bytes(read(bytes, self.weights_start() + entry * 4));
+                    (unpack(code), weight)
+                })
+                .collect();
+            model.insert(*feature, group);
+        }
+        let extras_start = self.weights_start() + self.len() * 4;
+        let strings_start = extras_start + extra_count(bytes) * EXTRA_LEN;
+        for record in bytes[extras_start..strings_start].chunks_exact(EXTRA_LEN) {
+            let feature = FeatureKey::ALL[read_usize(record, 0)];
+            let start = strings_start + read_usize(record, 4);
+            let ngram = &bytes[start..start + read_usize(record, 8)];
+            let weight = i32::from_le_bytes(read(record, 12));
+            model
+                .entry(feature)
+                .or_default()
+                .insert(str::from_utf8(ngram).unwrap_or_default().into(), weight);
+        }
+        model
+    }
+
+    const fn weights_start(&self) -> usize {
+        KEYS_START + self.len() * 8
+    }
+}
+
+/// Packed entries of a [`CompiledModel`], borrowed from its buffer.
+#[derive(Clone, Copy, Debug)]
+pub struct Lookup<'a> {
+    offsets: &'a [usize; FeatureKey::ALL.len() + 1],
+    keys: &'a [[u8; 8]],
+    weights: &'a [[u8; 4]],
+}
+
+impl Lookup<'_> {
+    /// Look up the weight of a packed n-gram under `feature`, or `0` when absent.
+    pub fn weight(&self, feature: FeatureKey, code: u64) -> i32 {
+        let index = feature as usize;
+        let start = self.offsets[index];
+        self.keys[start..self.offsets[index + 1]]
+            .binary_search_by(|key| u64::from_le_bytes(*key).cmp(&code))
+            .map_or(0, |found| i32::from_le_bytes(self.weights[start + found]))
+    }
+}
+
+impl From<Model> for CompiledModel {
+    fn from(model: Model) -> Self {
+        Self::new(&model)
+    }
+}
+
+impl PartialEq for CompiledModel {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl fmt::Debug for CompiledModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompiledModel")
+            .field("len", &self.len())
+            .field("base_score", &self.base_score)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Check that `bytes` is a well-formed binary model.
+fn validate(bytes: &[u8]) -> Result<(), ModelError> {
+    if bytes.len() < KEYS_START || &bytes[..4] != MAGIC {
+        return Err(ModelError::InvalidBinary("missing header"));
+    }
+    let version = u16::from_le_bytes(read(bytes, 4));
+    if version != VERSION {
+        return Err(ModelError::UnsupportedVersion(version));
+    }
+    let entries = read_usize(bytes, 8);
+    let extras = extra_count(bytes);
+    let string_len = read_usize(bytes, 24);
+    let expected_len = entries
+        .checked_mul(12)
+        .and_then(|len| len.checked_add(extras.checked_mul(EXTRA_LEN)?))
+        .and_then(|len| len.checked_add(string_len))
+        .and_then(|len| len.checked_add(KEYS_START));
+    if expected_len != Some(bytes.len()) {
+        return Err(ModelError::InvalidBinary("length does not match header"));
+    }
+
+    let (offsets, _) = read_header(bytes);
+    if offsets[0] != 0
+        || offsets[FeatureKey::ALL.len()] != entries
+        || offsets.windows(2).any(|pair| pair[0] > pair[1])
+    {
+        return Err(ModelError::InvalidBinary("invalid feature offsets"));
+    }
+    let (keys, _) = bytes[KEYS_START..KEYS_START + entries * 8].as_chunks::<8>();
+    if keys.iter().any(|key| !is_packed(u64::from_le_bytes(*key))) {
+        return Err(ModelError::InvalidBinary("invalid packed n-gram"));
+    }
+    for pair in offsets.windows(2) {
+        if keys[pair[0]..pair[1]]
+            .windows(2)
+            .any(|keys| u64::from_le_bytes(keys[0]) >= u64::from_le_bytes(keys[1]))
+        {
+            return Err(ModelError::InvalidBinary("packed entries are not sorted"));
+        }
+    }
+
+    let extras_start = KEYS_START + entries * 12;
+    let strings = &bytes[extras_start + extras * EXTRA_LEN..];
+    for record in bytes[extras_start..extras_start + extras * EXTRA_LEN].chunks_exact(EXTRA_LEN) {
+        let start = read_usize(record, 4);
+        let ngram = start
+            .checked_add(read_usize(record, 8))
+            .and_then(|end| strings.get(start..end));
+        if read_usize(record, 0) >= FeatureKey::ALL.len()
+            || ngram.is_none_or(|ngram| str::from_utf8(ngram).is_err())
+        {
+            return Err(ModelError::InvalidBinary("invalid string table entry"));
+        }
+    }
+    Ok(())
+}
+
+/// Read the feature offsets and the base score from the header of `bytes`.
+const fn read_header(bytes: &[u8]) -> ([usize; FEATURES + 1], f64) {
+    let mut offsets = [0; FEATURES + 1];
+    let mut index = 0;
+    while index < offsets.len() {
+        offsets[index] = read_usize(bytes, HEADER_LEN + index * 4);
+        index += 1;
+    }
+    let total = i64::from_le_bytes(read(bytes, 16));
+    #[expect(clippy::cast_precision_loss)]
+    let base_score = -0.5 * total as f64;
+    (offsets, base_score)
+}
+
+const fn extra_count(bytes: &[u8]) -> usize {
+    read_usize(bytes, 12)
+}
+
+const fn read<const N: usize>(bytes: &[u8], position: usize) -> [u8; N] {
+    let mut value = [0; N];
+    let mut index = 0;
+    while index < N {
+        value[index] = bytes[position + index];
+        index += 1;
+    }
+    value
+}
+
+const fn read_usize(bytes: &[u8], position: usize) -> usize {
+    u32::from_le_bytes(read(bytes, position)) as usize
+}
+
+/// Whether `code` is the packed form of a valid n-gram.
+fn is_packed(mut code: u64) -> bool {
+    let mut len = 0;
+    while code != 0 {
+        let valid = u32::try_from((code & CHAR_MASK).wrapping_sub(1))
+            .ok()
+            .and_then(char::from_u32)
+            .is_some();
+        if !valid || len == MAX_NGRAM_CHARS {
+            return false;
+        }
+        len += 1;
+        code >>= CHAR_BITS;
+    }
+    true
+}
+
+fn unpack(mut code: u64) -> String {
+    let mut chars = ['\0'; MAX_NGRAM_CHARS];
+    let mut len = 0;
+    while code != 0 {
+        let value = u32::try_from((code & CHAR_MASK) - 1).unwrap_or_default();
+        chars[len] = char::from_u32(value).unwrap_or_default();
+        len += 1;
+        code >>= CHAR_BITS;
+    }
+    chars[..len].iter().rev().collect()
+}
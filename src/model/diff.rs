@@ -0,0 +1,115 @@
+//! Comparing models.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::Serialize;
+
+use super::{FeatureKey, InnerModel, Model};
+
+/// Result of [`diff_models`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ModelDiff {
+    /// Changes of every feature key that has any, in [`FeatureKey::ALL`] order.
+    pub features: Vec<FeatureDiff>,
+}
+
+/// Changed entries of one feature key, each sorted by n-gram.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FeatureDiff {
+    /// Feature key.
+    pub feature: FeatureKey,
+    /// Entries only in the new model.
+    pub added: Vec<NgramWeight>,
+    /// Entries only in the old model.
+    pub removed: Vec<NgramWeight>,
+    /// Entries in both models with different weights.
+    pub changed: Vec<WeightChange>,
+}
+
+/// N-gram and its weight.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct NgramWeight {
+    /// N-gram.
+    pub ngram: String,
+    /// Weight.
+    pub weight: i32,
+}
+
+/// N-gram whose weight changed.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct WeightChange {
+    /// N-gram.
+    pub ngram: String,
+    /// Weight in the old model.
+    pub old: i32,
+    /// Weight in the new model.
+    pub new: i32,
+}
+
+impl ModelDiff {
+    /// Return whether the models have the same entries.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+}
+
+/// List the entries added, removed and changed from `old` to `new`.
+///
+/// An entry with a zero weight is reported like any other, even though it
+/// has no effect on parsing.
+#[must_use]
+pub fn diff_models(old: &Model, new: &Model) -> ModelDiff {
+    let empty = InnerModel::new();
+    let mut features = Vec::new();
+    for feature in FeatureKey::ALL {
+        let old_group = old.get(&feature).unwrap_or(&empty);
+        let new_group = new.get(&feature).unwrap_or(&empty);
+
+        let mut added: Vec<NgramWeight> = new_group
+            .iter()
+            .filter(|(ngram, _)| !old_group.contains_key(*ngram))
+            .map(|(ngram, weight)| NgramWeight {
+                ngram: ngram.clone(),
+                weight: *weight,
+            })
+            .collect();
+        let mut removed: Vec<NgramWeight> = old_group
+            .iter()
+            .filter(|(ngram, _)| !new_group.contains_key(*ngram))
+            .map(|(ngram, weight)| NgramWeight {
+                ngram: ngram.clone(),
+                weight: *weight,
+            })
+            .collect();
+        let mut changed: Vec<WeightChange> = old_group
+            .iter()
+            .filter_map(|(ngram, old)| {
+                let new = *new_group.get(ngram)?;
+                (new != *old).then(|| WeightChange {
+                    ngram: ngram.clone(),
+                    old: *old,
+                    new,
+                })
+            })
+            .collect();
+        if added.is_empty() && removed.is_empty() && changed.is_empty() {
+            continue;
+        }
+        added.sort_unstable_by(|a, b| a.ngram.cmp(&b.ngram));
+        removed.sort_unstable_by(|a, b| a.ngram.cmp(&b.ngram));
+        changed.sort_unstable_by(|a, b| a.ngram.cmp(&b.ngram));
+        features.push(FeatureDiff {
+            feature,
+            added,
+            removed,
+            changed,
+        });
+    }
+    ModelDiff { features }
+}
@@ -0,0 +1,223 @@
+//! Model validation.
+//!
+//! [`parse_model_json`](super::parse_model_json) accepts any n-gram and
+//! any `i32` weight, so a model can contain entries that never take effect.
+//! [`lint`] reports them, along with entries that are likely mistakes.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::Serialize;
+
+use super::{BASE_SCORE_NGRAM, FeatureKey, Model};
+use crate::parser::{FEATURE_WINDOWS, feature_window};
+
+/// How serious a [`LintIssue`] is.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The entry is suspicious but can still affect parsing.
+    Warning,
+    /// The entry can never affect parsing, or breaks other implementations.
+    Error,
+}
+
+/// Problem found by [`lint`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct LintIssue {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// What the problem is.
+    #[serde(flatten)]
+    pub kind: LintKind,
+}
+
+/// Kind of a [`LintIssue`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LintKind {
+    /// The n-gram is empty or the feature window never has as many characters
+    /// as the n-gram, so the entry never matches, e.g. a trigram under a `UW*`
+    /// key.
+    Unreachable {
+        /// Feature key of the entry.
+        feature: FeatureKey,
+        /// N-gram of the entry.
+        ngram: String,
+    },
+    /// The n-gram is shorter than the feature's unigram, bigram or trigram
+    /// arity, so it only matches where the window is cut by a sentence edge.
+    EdgeOnly {
+        /// Feature key of the entry.
+        feature: FeatureKey,
+        /// N-gram of the entry.
+        ngram: String,
+    },
+    /// The entry has a zero weight and has no effect.
+    ZeroWeight {
+        /// Feature key of the entry.
+        feature: FeatureKey,
+        /// N-gram of the entry.
+        ngram: String,
+    },
+    /// Several n-grams of one feature are equal once full-width ASCII and the
+    /// ideographic space are folded to ASCII and letters are lowercased.
+    DuplicateAfterNormalization {
+        /// Feature key of the entries.
+        feature: FeatureKey,
+        /// The equivalent n-grams, sorted.
+        ngrams: Vec<String>,
+    },
+    /// The sum of all weights, from which the base score is derived, does not
+    /// fit in an `i32` and overflows implementations that sum in 32 bits.
+    BaseScoreOverflow {
+        /// Sum of all weights.
+        total: i64,
+    },
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreachable { feature, ngram } => {
+                write!(f, "{feature} {ngram:?} can never match the feature window")
+            }
+            Self::EdgeOnly { feature, ngram } => {
+                write!(f, "{feature} {ngram:?} only matches at sentence edges")
+            }
+            Self::ZeroWeight { feature, ngram } => {
+                write!(f, "{feature} {ngram:?} has a zero weight")
+            }
+            Self::DuplicateAfterNormalization { feature, ngrams } => {
+                write!(
+                    f,
+                    "{feature} n-grams are equal after normalization: {ngrams:?}"
+                )
+            }
+            Self::BaseScoreOverflow { total } => {
+                write!(f, "sum of all weights {total} overflows i32")
+            }
+        }
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}", self.kind)
+    }
+}
+
+/// Check `model` for entries that never match or are likely mistakes.
+///
+/// Issues are ordered by feature key in [`FeatureKey::ALL`] order, then by
+/// n-gram, with base score problems last.
+#[must_use]
+pub fn lint(model: &Model) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    for &(feature, start, end) in &FEATURE_WINDOWS {
+        let Some(group) = model.get(&feature) else {
+            continue;
+        };
+        let reachable = reachable_lengths(start, end);
+        let mut entries: Vec<(&String, i32)> = group
+            .iter()
+            .map(|(ngram, weight)| (ngram, *weight))
+            .collect();
+        entries.sort_unstable();
+
+        let mut normalized: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (ngram, weight) in entries {
+            let chars = ngram.chars().count();
+            // The entry kept by `prune_model` only counts towards the base score.
+            let base_score = feature == FeatureKey::UW1 && ngram == BASE_SCORE_NGRAM;
+            if !base_score && !reachable.get(chars).copied().unwrap_or_default() {
+                issues.push(error(LintKind::Unreachable {
+                    feature,
+                    ngram: ngram.clone(),
+                }));
+            } else if chars < end - start {
+                issues.push(warning(LintKind::EdgeOnly {
+                    feature,
+                    ngram: ngram.clone(),
+                }));
+            }
+            if weight == 0 {
+                issues.push(warning(LintKind::ZeroWeight {
+                    feature,
+                    ngram: ngram.clone(),
+                }));
+            }
+            normalized
+                .entry(normalize(ngram))
+                .or_default()
+                .push(ngram.clone());
+        }
+        issues.extend(
+            normalized
+                .into_values()
+                .filter(|ngrams| ngrams.len() > 1)
+                .map(|ngrams| warning(LintKind::DuplicateAfterNormalization { feature, ngrams })),
+        );
+    }
+
+    let total: i64 = model
+        .values()
+        .flat_map(|group| group.values())
+        .map(|weight| i64::from(*weight))
+        .sum();
+    if i32::try_from(total).is_err() {
+        issues.push(error(LintKind::BaseScoreOverflow { total }));
+    }
+    issues
+}
+
+const fn error(kind: LintKind) -> LintIssue {
+    LintIssue {
+        severity: Severity::Error,
+        kind,
+    }
+}
+
+const fn warning(kind: LintKind) -> LintIssue {
+    LintIssue {
+        severity: Severity::Warning,
+        kind,
+    }
+}
+
+/// Which n-gram lengths a feature window can have, indexed by length.
+///
+/// Windows span at most six characters around the boundary, so sentences of
+/// up to seven characters cover every way they can be cut by an edge. Empty
+/// windows are never looked up, so the empty n-gram is never reachable.
+fn reachable_lengths(start: usize, end: usize) -> [bool; 4] {
+    let mut reachable = [false; 4];
+    for len in 2..=7 {
+        for i in 1..len {
+            reachable[feature_window(i, len, start, end).len()] = true;
+        }
+    }
+    reachable[0] = false;
+    reachable
+}
+
+fn normalize(ngram: &str) -> String {
+    ngram
+        .chars()
+        .map(|ch| match ch {
+            '\u{3000}' => ' ',
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(u32::from(ch) - 0xFEE0).unwrap_or(ch),
+            _ => ch,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
@@ -0,0 +1,73 @@
+//! Combining models.
+
+use super::{FeatureKey, InnerModel, Model};
+
+/// How [`merge_models`] combines the weights of two models.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MergeStrategy {
+    /// Add the weights of both models, saturating at the `i32` bounds.
+    Sum,
+    /// Take the weight from the second model wherever it has an entry.
+    Override,
+    /// Interpolate linearly: `(1 - weight) * base + weight * other`, rounded.
+    ///
+    /// An entry missing from one model counts as zero there.
+    Interpolate {
+        /// Share of the second model, usually between `0.0` and `1.0`.
+        weight: f64,
+    },
+}
+
+/// Merge `other` into `base` with the given strategy.
+///
+/// Entries whose merged weight is zero are dropped, since they have no effect
+/// on parsing. Merging changes the
+/// [base score](super::CompiledModel::base_score) as well.
+#[must_use]
+pub fn merge_models(base: &Model, other: &Model, strategy: MergeStrategy) -> Model {
+    let mut merged = Model::with_capacity(FeatureKey::ALL.len());
+    for feature in FeatureKey::ALL {
+        let empty = InnerModel::new();
+        let base_group = base.get(&feature).unwrap_or(&empty);
+        let other_group = other.get(&feature).unwrap_or(&empty);
+        let ngrams = base_group.keys().chain(
+            other_group
+                .keys()
+                .filter(|ngram| !base_group.contains_key(*ngram)),
+        );
+        let group: InnerModel = ngrams
+            .filter_map(|ngram| {
+                let base_weight = base_group.get(ngram).copied();
+                let other_weight = other_group.get(ngram).copied();
+                let weight = merge_weights(base_weight, other_weight, strategy);
+                (weight != 0).then(|| (ngram.clone(), weight))
+            })
+            .collect();
+        if !group.is_empty() {
+            merged.insert(feature, group);
+        }
+    }
+    merged
+}
+
+fn merge_weights(base: Option<i32>, other: Option<i32>, strategy: MergeStrategy) -> i32 {
+    match strategy {
+        MergeStrategy::Sum => base.unwrap_or(0).saturating_add(other.unwrap_or(0)),
+        MergeStrategy::Override => other.or(base).unwrap_or(0),
+        MergeStrategy::Interpolate { weight } => {
+            // `f64::mul_add` and `f64::round` are not available in `core`.
+            let base = f64::from(base.unwrap_or(0));
+            #[cfg_attr(feature = "std", expect(clippy::suboptimal_flops))]
+            let value = base + weight * (f64::from(other.unwrap_or(0)) - base);
+            let rounded = if value < 0.0 {
+                value - 0.5
+            } else {
+                value + 0.5
+            };
+            #[expect(clippy::cast_possible_truncation)]
+            let rounded = rounded as i32;
+            rounded
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Shrinking models.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use super::{FeatureKey, InnerModel, Model};
+
+/// N-gram of the `UW1` entry that [`prune_model`] adds to keep the base score.
+///
+/// It is longer than any feature window, so it never matches and only counts
+/// towards the [base score](super::CompiledModel::base_score).
+pub const BASE_SCORE_NGRAM: &str = "<base score>";
+
+/// Options for [`prune_model`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PruneOptions {
+    /// Drop entries whose absolute weight is below this value.
+    pub min_weight: Option<u32>,
+    /// Keep at most this many entries per feature key, those with the largest
+    /// absolute weights.
+    pub top_k: Option<usize>,
+}
+
+/// Integer width for [`quantize_model`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Quantization {
+    /// Weights fit in an `i8`.
+    I8,
+    /// Weights fit in an `i16`.
+    I16,
+}
+
+impl Quantization {
+    const fn max(self) -> u32 {
+        match self {
+            Self::I8 => i8::MAX.unsigned_abs() as u32,
+            Self::I16 => i16::MAX.unsigned_abs() as u32,
+        }
+    }
+}
+
+/// Result of [`quantize_model`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuantizedModel {
+    /// Model with the quantized weights.
+    pub model: Model,
+    /// Factor by which the original weights were divided.
+    pub scale: u32,
+}
+
+/// Drop low-magnitude entries from `model`.
+///
+/// Entries are dropped below [`min_weight`](PruneOptions::min_weight) first,
+/// then [`top_k`](PruneOptions::top_k) is applied to each feature key, with
+/// ties broken by n-gram. Zero weights are always dropped.
+///
+/// The [base score](super::CompiledModel::base_score) is kept: the sum of the
+/// dropped weights is added to the `UW1` entry for [`BASE_SCORE_NGRAM`],
+/// which `top_k` does not count.
+#[must_use]
+pub fn prune_model(model: &Model, options: &PruneOptions) -> Model {
+    let min_weight = options.min_weight.unwrap_or(1).max(1);
+    let mut pruned = Model::with_capacity(model.len());
+    for (feature, group) in model {
+        let mut entries: Vec<_> = group
+            .iter()
+            .filter(|(_, weight)| weight.unsigned_abs() >= min_weight)
+            .collect();
+        if let Some(top_k) = options.top_k
+            && entries.len() > top_k
+        {
+            entries.sort_unstable_by(|(a_ngram, a_weight), (b_ngram, b_weight)| {
+                b_weight
+                    .unsigned_abs()
+                    .cmp(&a_weight.unsigned_abs())
+                    .then_with(|| a_ngram.cmp(b_ngram))
+            });
+            entries.truncate(top_k);
+        }
+        if !entries.is_empty() {
+            let group: InnerModel = entries
+                .into_iter()
+                .map(|(ngram, weight)| (ngram.clone(), *weight))
+                .collect();
+            pruned.insert(*feature, group);
+        }
+    }
+
+    let dropped = weight_sum(model) - weight_sum(&pruned);
+    if dropped != 0 {
+        let weight = pruned
+            .entry(FeatureKey::UW1)
+            .or_default()
+            .entry(BASE_SCORE_NGRAM.to_string())
+            .or_insert(0);
+        let total = i64::from(*weight) + dropped;
+        *weight = i32::try_from(total).unwrap_or(if total < 0 { i32::MIN } else { i32::MAX });
+    }
+    pruned
+}
+
+fn weight_sum(model: &Model) -> i64 {
+    model
+        .values()
+        .flat_map(InnerModel::values)
+        .map(|weight| i64::from(*weight))
+        .sum()
+}
+
+/// Scale the weights of `model` down to fit in `quantization`.
+///
+/// Every weight is divided by the smallest integer scale that brings the
+/// largest magnitude into range and rounded half away from zero; weights that
+/// round to zero are dropped. Since all weights and the base score shrink by
+/// the same factor, the quantized model segments like the original one up to
+/// rounding when the threshold is `0.0`. Other thresholds must be divided by
+/// [`scale`](QuantizedModel::scale).
+#[must_use]
+pub fn quantize_model(model: &Model, quantization: Quantization) -> QuantizedModel {
+    let max = quantization.max();
+    let largest = model
+        .values()
+        .flat_map(InnerModel::values)
+        .map(|weight| weight.unsigned_abs())
+        .max()
+        .unwrap_or(0);
+    let scale = largest.div_ceil(max).max(1);
+
+    let mut quantized = Model::with_capacity(model.len());
+    for (feature, group) in model {
+        let group: InnerModel = group
+            .iter()
+            .filter_map(|(ngram, weight)| {
+                let magnitude =
+                    (u64::from(weight.unsigned_abs()) + u64::from(scale / 2)) / u64::from(scale);
+                let rounded = i32::try_from(magnitude.min(u64::from(max))).ok()? * weight.signum();
+                (rounded != 0).then(|| (ngram.clone(), rounded))
+            })
+            .collect();
+        if !group.is_empty() {
+            quantized.insert(*feature, group);
+        }
+    }
+    QuantizedModel {
+        model: quantized,
+        scale,
+    }
+}
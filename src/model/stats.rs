@@ -0,0 +1,293 @@
+//! Model statistics.
+//!
+//! [`model_stats`] summarizes a [`Model`]: how many entries each feature key
+//! has, how their weights are distributed, which entries weigh the most and
+//! which writing systems the unigram features cover.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+
+use serde::Serialize;
+
+use super::{FeatureKey, Model};
+
+/// Result of [`model_stats`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ModelStats {
+    /// Total number of entries.
+    pub entries: usize,
+    /// Score of a boundary before any feature matches.
+    pub base_score: f64,
+    /// Distribution of all weights, or `None` for an empty model.
+    pub weights: Option<WeightStats>,
+    /// Entries and weight distribution of every feature key, in
+    /// [`FeatureKey::ALL`] order.
+    pub features: Vec<FeatureStats>,
+    /// Entries with the largest positive weights, strongest first.
+    pub strongest_positive: Vec<WeightedNgram>,
+    /// Entries with the largest negative weights, strongest first.
+    pub strongest_negative: Vec<WeightedNgram>,
+    /// Distinct characters of the unigram features per script, most first.
+    pub scripts: Vec<ScriptCoverage>,
+}
+
+/// Statistics of one feature key.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FeatureStats {
+    /// Feature key.
+    pub feature: FeatureKey,
+    /// Number of entries.
+    pub entries: usize,
+    /// Distribution of the weights, or `None` without entries.
+    pub weights: Option<WeightStats>,
+}
+
+/// Distribution of a set of weights.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WeightStats {
+    /// Smallest weight.
+    pub min: i32,
+    /// First quartile.
+    pub q1: i32,
+    /// Median weight.
+    pub median: i32,
+    /// Third quartile.
+    pub q3: i32,
+    /// Largest weight.
+    pub max: i32,
+    /// Mean weight.
+    pub mean: f64,
+    /// Number of positive weights, which favor a boundary.
+    pub positive: usize,
+    /// Number of negative weights, which suppress a boundary.
+    pub negative: usize,
+}
+
+/// A model entry.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct WeightedNgram {
+    /// Feature key.
+    pub feature: FeatureKey,
+    /// N-gram.
+    pub ngram: String,
+    /// Weight.
+    pub weight: i32,
+}
+
+/// Number of distinct characters of one script in the unigram features.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ScriptCoverage {
+    /// Script.
+    pub script: Script,
+    /// Number of distinct characters.
+    pub characters: usize,
+}
+
+/// Writing system of a character, as far as the bundled models need.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Script {
+    /// Hiragana.
+    Hiragana,
+    /// Katakana, including half-width forms.
+    Katakana,
+    /// CJK ideographs.
+    Han,
+    /// Hangul.
+    Hangul,
+    /// Thai.
+    Thai,
+    /// Latin letters, including full-width forms.
+    Latin,
+    /// Decimal digits, including full-width forms.
+    Digit,
+    /// Punctuation, symbols and spaces.
+    Punctuation,
+    /// Any other character.
+    Other,
+}
+
+impl Script {
+    /// Classify a character.
+    #[must_use]
+    pub const fn of(ch: char) -> Self {
+        match ch {
+            '\u{3041}'..='\u{309F}' => Self::Hiragana,
+            '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+                Self::Katakana
+            }
+            '\u{3005}'
+            | '\u{3007}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{3FFFF}' => Self::Han,
+            '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+                Self::Hangul
+            }
+            '\u{0E00}'..='\u{0E7F}' => Self::Thai,
+            'A'..='Z'
+            | 'a'..='z'
+            | '\u{00C0}'..='\u{024F}'
+            | '\u{FF21}'..='\u{FF3A}'
+            | '\u{FF41}'..='\u{FF5A}' => Self::Latin,
+            '0'..='9' | '\u{FF10}'..='\u{FF19}' => Self::Digit,
+            '\u{0000}'..='\u{00BF}'
+            | '\u{2000}'..='\u{2BFF}'
+            | '\u{3000}'..='\u{303F}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FF65}' => Self::Punctuation,
+            _ => Self::Other,
+        }
+    }
+
+    /// Return the name of the script.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Hiragana => "hiragana",
+            Self::Katakana => "katakana",
+            Self::Han => "han",
+            Self::Hangul => "hangul",
+            Self::Thai => "thai",
+            Self::Latin => "latin",
+            Self::Digit => "digit",
+            Self::Punctuation => "punctuation",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Summarize `model`, listing up to `top` strongest entries of each sign.
+///
+/// Ties between entries of equal weight are broken by feature key, then by
+/// n-gram, so the result is stable.
+#[must_use]
+pub fn model_stats(model: &Model, top: usize) -> ModelStats {
+    let mut entries: Vec<WeightedNgram> = FeatureKey::ALL
+        .iter()
+        .filter_map(|feature| Some((*feature, model.get(feature)?)))
+        .flat_map(|(feature, group)| {
+            group.iter().map(move |(ngram, weight)| WeightedNgram {
+                feature,
+                ngram: ngram.clone(),
+                weight: *weight,
+            })
+        })
+        .collect();
+    entries.sort_unstable_by(|a, b| {
+        (a.feature as usize, &a.ngram).cmp(&(b.feature as usize, &b.ngram))
+    });
+
+    let features = FeatureKey::ALL
+        .iter()
+        .map(|&feature| {
+            let weights: Vec<i32> = entries
+                .iter()
+                .filter(|entry| entry.feature == feature)
+                .map(|entry| entry.weight)
+                .collect();
+            FeatureStats {
+                feature,
+                entries: weights.len(),
+                weights: WeightStats::new(weights),
+            }
+        })
+        .collect();
+
+    let total: i64 = entries.iter().map(|entry| i64::from(entry.weight)).sum();
+    #[expect(clippy::cast_precision_loss)]
+    let base_score = -0.5 * total as f64;
+
+    let mut characters: BTreeMap<Script, Vec<char>> = BTreeMap::new();
+    for entry in &entries {
+        let mut chars = entry.ngram.chars();
+        if let (Some(ch), None, true) = (chars.next(), chars.next(), is_unigram(entry.feature)) {
+            characters.entry(Script::of(ch)).or_default().push(ch);
+        }
+    }
+    let mut scripts: Vec<ScriptCoverage> = characters
+        .into_iter()
+        .map(|(script, mut chars)| {
+            chars.sort_unstable();
+            chars.dedup();
+            ScriptCoverage {
+                script,
+                characters: chars.len(),
+            }
+        })
+        .collect();
+    scripts.sort_by_key(|coverage| Reverse(coverage.characters));
+
+    let mut strongest_positive: Vec<WeightedNgram> = entries
+        .iter()
+        .filter(|entry| entry.weight > 0)
+        .cloned()
+        .collect();
+    strongest_positive.sort_by_key(|entry| Reverse(entry.weight));
+    strongest_positive.truncate(top);
+    let mut strongest_negative: Vec<WeightedNgram> = entries
+        .iter()
+        .filter(|entry| entry.weight < 0)
+        .cloned()
+        .collect();
+    strongest_negative.sort_by_key(|entry| entry.weight);
+    strongest_negative.truncate(top);
+
+    ModelStats {
+        entries: entries.len(),
+        base_score,
+        weights: WeightStats::new(entries.iter().map(|entry| entry.weight).collect()),
+        features,
+        strongest_positive,
+        strongest_negative,
+        scripts,
+    }
+}
+
+impl WeightStats {
+    fn new(mut weights: Vec<i32>) -> Option<Self> {
+        weights.sort_unstable();
+        let last = weights.len().checked_sub(1)?;
+        let total: i64 = weights.iter().copied().map(i64::from).sum();
+        #[expect(clippy::cast_precision_loss)]
+        let mean = total as f64 / weights.len() as f64;
+        Some(Self {
+            min: weights[0],
+            q1: weights[last / 4],
+            median: weights[last / 2],
+            q3: weights[last * 3 / 4],
+            max: weights[last],
+            mean,
+            positive: weights.iter().filter(|weight| **weight > 0).count(),
+            negative: weights.iter().filter(|weight| **weight < 0).count(),
+        })
+    }
+}
+
+const fn is_unigram(feature: FeatureKey) -> bool {
+    matches!(
+        feature,
+        FeatureKey::UW1
+            | FeatureKey::UW2
+            | FeatureKey::UW3
+            | FeatureKey::UW4
+            | FeatureKey::UW5
+            | FeatureKey::UW6
+    )
+}
@@ -1,38 +1,158 @@
-use alloc::string::String;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
 use alloc::vec::Vec;
+use core::ops::Range;
 
 use crate::Model;
-use crate::model::FeatureKey;
+use crate::dictionary::Dictionary;
+use crate::model::{CompiledModel, FeatureKey, Lookup, MergeStrategy, merge_models, pack_chars};
+
+/// Options for [`Parser`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParserOptions {
+    /// Score a position must exceed to become a boundary.
+    ///
+    /// Raise it for fewer, longer chunks and lower it for more, shorter ones.
+    pub threshold: f64,
+    /// Minimum number of characters in a chunk.
+    ///
+    /// Boundaries that would leave a shorter chunk are dropped.
+    pub min_chunk_chars: Option<usize>,
+    /// Maximum number of characters in a chunk.
+    ///
+    /// A chunk that would grow longer is broken at its highest-scoring
+    /// position, even if that score is below the threshold. This takes
+    /// precedence over [`min_chunk_chars`](Self::min_chunk_chars).
+    pub max_chunk_chars: Option<usize>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.0,
+            min_chunk_chars: None,
+            max_chunk_chars: None,
+        }
+    }
+}
 
 /// `BudouX` parser for semantic line breaks.
+///
+/// Cloning a [`CompiledModel`] shares its data, so cloning a parser or
+/// creating several parsers from one compiled model does not copy the model.
 #[derive(Clone, Debug)]
 pub struct Parser {
-    model: Model,
-    base_score: f64,
+    model: CompiledModel,
+    options: ParserOptions,
+    dictionary: Option<Arc<Dictionary>>,
 }
 
 impl Parser {
     /// Create a new parser from a model.
+    ///
+    /// The model is compiled into a [`CompiledModel`] first.
     #[must_use]
     pub fn new(model: Model) -> Self {
-        let total: f64 = model
-            .values()
-            .flat_map(|group| group.values())
-            .map(|value| f64::from(*value))
-            .sum();
-        let base_score = -0.5 * total;
-        Self { model, base_score }
+        Self::from_compiled(CompiledModel::from(model))
+    }
+
+    /// Create a new parser from an already compiled model.
+    #[must_use]
+    pub fn from_compiled(model: CompiledModel) -> Self {
+        Self {
+            model,
+            options: ParserOptions::default(),
+            dictionary: None,
+        }
+    }
+
+    /// Replace the options of this parser.
+    #[must_use]
+    pub const fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Apply a user dictionary after scoring.
+    ///
+    /// Boundaries inside [`Rule::Keep`](crate::dictionary::Rule::Keep)
+    /// phrases are removed and boundaries around
+    /// [`Rule::Break`](crate::dictionary::Rule::Break) phrases are added,
+    /// after the options have been applied, so the dictionary takes
+    /// precedence over the threshold and the chunk length limits.
+    #[must_use]
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(Arc::new(dictionary));
+        self
+    }
+
+    /// Stack an override layer on top of this parser's model.
+    ///
+    /// Every entry of `overrides` replaces the weight of the same n-gram in
+    /// the model, as with [`MergeStrategy::Override`], and the result is
+    /// compiled into a new model. The options and the dictionary are kept.
+    #[must_use]
+    pub fn with_overrides(&self, overrides: &Model) -> Self {
+        let model = merge_models(&self.model.to_model(), overrides, MergeStrategy::Override);
+        Self {
+            model: CompiledModel::from(model),
+            options: self.options.clone(),
+            dictionary: self.dictionary.clone(),
+        }
+    }
+
+    /// Return the options used by this parser.
+    #[must_use]
+    pub const fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// Return the user dictionary used by this parser, if any.
+    #[must_use]
+    pub fn dictionary(&self) -> Option<&Dictionary> {
+        self.dictionary.as_deref()
+    }
+
+    /// Return the compiled model used by this parser.
+    #[must_use]
+    pub const fn compiled_model(&self) -> &CompiledModel {
+        &self.model
     }
 
     /// Split a sentence into semantic chunks.
     #[must_use]
     pub fn parse(&self, sentence: &str) -> Vec<String> {
+        self.parse_str(sentence).map(ToString::to_string).collect()
+    }
+
+    /// Split a sentence into chunks borrowed from `sentence`.
+    ///
+    /// This is the allocation-light counterpart of [`parse`](Self::parse).
+    pub fn parse_str<'a>(&self, sentence: &'a str) -> impl Iterator<Item = &'a str> + use<'a> {
+        self.parse_spans(sentence)
+            .into_iter()
+            .map(move |span| &sentence[span])
+    }
+
+    /// Return the byte range of every chunk in the sentence.
+    ///
+    /// The ranges are contiguous and cover the whole sentence, so each can be
+    /// used to slice `sentence` directly.
+    #[must_use]
+    pub fn parse_spans(&self, sentence: &str) -> Vec<Range<usize>> {
         if sentence.is_empty() {
             return Vec::new();
         }
-        let chars: Vec<char> = sentence.chars().collect();
-        let boundaries = self.parse_boundaries_from_chars(&chars);
-        split_by_boundaries(&chars, &boundaries)
+        let boundaries = self.parse_boundaries_bytes(sentence);
+        let mut spans = Vec::with_capacity(boundaries.len() + 1);
+        let mut start = 0;
+        for boundary in boundaries {
+            spans.push(start..boundary);
+            start = boundary;
+        }
+        spans.push(start..sentence.len());
+        spans
     }
 
     /// Return the boundary indices for the sentence.
@@ -44,81 +164,129 @@ impl Parser {
         self.parse_boundaries_from_chars(&chars)
     }
 
-    fn parse_boundaries_from_chars(&self, chars: &[char]) -> Vec<usize> {
-        let mut result = Vec::new();
-        let len = chars.len();
-        if len == 0 {
-            return result;
+    /// Return the boundary indices for the sentence as byte offsets.
+    ///
+    /// Every offset lies on a `char` boundary of `sentence`.
+    #[must_use]
+    pub fn parse_boundaries_bytes(&self, sentence: &str) -> Vec<usize> {
+        let (offsets, chars): (Vec<usize>, Vec<char>) = sentence.char_indices().unzip();
+        let mut boundaries = self.parse_boundaries_from_chars(&chars);
+        for boundary in &mut boundaries {
+            *boundary = offsets[*boundary];
         }
+        boundaries
+    }
 
-        for i in 1..len {
-            let mut score = self.base_score;
-            score += self.weight(
-                FeatureKey::UW1,
-                &substring(chars, i.saturating_sub(3), i.saturating_sub(2)),
-            );
-            score += self.weight(
-                FeatureKey::UW2,
-                &substring(chars, i.saturating_sub(2), i.saturating_sub(1)),
-            );
-            score += self.weight(FeatureKey::UW3, &substring(chars, i.saturating_sub(1), i));
-            score += self.weight(FeatureKey::UW4, &substring(chars, i, (i + 1).min(len)));
-            score += self.weight(
-                FeatureKey::UW5,
-                &substring(chars, (i + 1).min(len), (i + 2).min(len)),
-            );
-            score += self.weight(
-                FeatureKey::UW6,
-                &substring(chars, (i + 2).min(len), (i + 3).min(len)),
-            );
-            score += self.weight(FeatureKey::BW1, &substring(chars, i.saturating_sub(2), i));
-            score += self.weight(
-                FeatureKey::BW2,
-                &substring(chars, i.saturating_sub(1), (i + 1).min(len)),
-            );
-            score += self.weight(FeatureKey::BW3, &substring(chars, i, (i + 2).min(len)));
-            score += self.weight(FeatureKey::TW1, &substring(chars, i.saturating_sub(3), i));
-            score += self.weight(
-                FeatureKey::TW2,
-                &substring(chars, i.saturating_sub(2), (i + 1).min(len)),
-            );
-            score += self.weight(
-                FeatureKey::TW3,
-                &substring(chars, i.saturating_sub(1), (i + 2).min(len)),
-            );
-            score += self.weight(FeatureKey::TW4, &substring(chars, i, (i + 3).min(len)));
-            if score > 0.0 {
-                result.push(i);
-            }
+    /// Score every candidate boundary between `chars`, then apply the
+    /// options and the dictionary.
+    ///
+    /// N-grams are packed into integers and looked up in the compiled model,
+    /// so scoring does not allocate per position.
+    pub(crate) fn parse_boundaries_from_chars(&self, chars: &[char]) -> Vec<usize> {
+        let len = chars.len();
+        let threshold = self.options.threshold;
+        let lookup = self.model.lookup();
+        let mut boundaries =
+            if self.options.min_chunk_chars.is_none() && self.options.max_chunk_chars.is_none() {
+                (1..len)
+                    .filter(|&i| self.score(&lookup, chars, i) > threshold)
+                    .collect()
+            } else {
+                let scores: Vec<f64> = (1..len).map(|i| self.score(&lookup, chars, i)).collect();
+                select_boundaries(&scores, &self.options)
+            };
+        if let Some(dictionary) = &self.dictionary {
+            dictionary.apply(chars, &mut boundaries);
         }
-        result
+        boundaries
     }
 
-    fn weight(&self, group: FeatureKey, key: &str) -> f64 {
-        f64::from(
-            self.model
-                .get(&group)
-                .and_then(|map| map.get(key))
-                .copied()
-                .unwrap_or(0),
-        )
+    /// Score the candidate boundary before `chars[i]`.
+    ///
+    /// Windows that are empty near the sentence edges have no feature, as in
+    /// [`encode`](crate::encode).
+    pub(crate) fn score(&self, lookup: &Lookup<'_>, chars: &[char], i: usize) -> f64 {
+        let len = chars.len();
+        let mut score = self.model.base_score();
+        for &(feature, start, end) in &FEATURE_WINDOWS {
+            let window = feature_window(i, len, start, end);
+            if window.is_empty() {
+                continue;
+            }
+            if let Some(code) = pack_chars(&chars[window]) {
+                score += f64::from(lookup.weight(feature, code));
+            }
+        }
+        score
     }
 }
 
-fn substring(chars: &[char], start: usize, end: usize) -> String {
-    if start >= end {
-        return String::new();
-    }
-    chars[start..end].iter().collect()
-}
+/// Pick boundaries from per-position scores under the chunk length options.
+///
+/// `scores[i - 1]` is the score of the boundary before the `i`-th character.
+/// Boundaries are taken greedily from the start: a position above the
+/// threshold is kept unless it would leave a chunk shorter than the minimum,
+/// and a chunk reaching the maximum is broken at its best-scoring position.
+fn select_boundaries(scores: &[f64], options: &ParserOptions) -> Vec<usize> {
+    let len = scores.len() + 1;
+    let (min, max) = chunk_limits(options);
 
-fn split_by_boundaries(chars: &[char], boundaries: &[usize]) -> Vec<String> {
     let mut result = Vec::new();
-    let mut start = 0;
-    for &boundary in boundaries {
-        result.push(chars[start..boundary].iter().collect());
-        start = boundary;
+    let mut last = 0;
+    for i in 1..len {
+        if i - last < min {
+            continue;
+        }
+        if scores[i - 1] > options.threshold && len - i >= min {
+            result.push(i);
+            last = i;
+        } else if i - last == max {
+            let start = last + min;
+            let end = if len - min >= start {
+                i.min(len - min)
+            } else {
+                i
+            };
+            let best = (start..=end)
+                .max_by(|a, b| scores[a - 1].total_cmp(&scores[b - 1]))
+                .unwrap_or(i);
+            result.push(best);
+            last = best;
+        }
     }
-    result.push(chars[start..].iter().collect());
     result
 }
+
+/// Return the effective minimum and maximum chunk lengths of `options`.
+pub fn chunk_limits(options: &ParserOptions) -> (usize, usize) {
+    let max = options.max_chunk_chars.unwrap_or(usize::MAX).max(1);
+    let min = options.min_chunk_chars.unwrap_or(1).clamp(1, max);
+    (min, max)
+}
+
+/// Character windows scored for each feature key.
+///
+/// Offsets are relative to three characters before the candidate boundary,
+/// so `(3, 4)` is the character right after the boundary. Windows are clamped
+/// to the sentence and may be shorter (or empty) near its ends.
+pub const FEATURE_WINDOWS: [(FeatureKey, usize, usize); 13] = [
+    (FeatureKey::UW1, 0, 1),
+    (FeatureKey::UW2, 1, 2),
+    (FeatureKey::UW3, 2, 3),
+    (FeatureKey::UW4, 3, 4),
+    (FeatureKey::UW5, 4, 5),
+    (FeatureKey::UW6, 5, 6),
+    (FeatureKey::BW1, 1, 3),
+    (FeatureKey::BW2, 2, 4),
+    (FeatureKey::BW3, 3, 5),
+    (FeatureKey::TW1, 0, 3),
+    (FeatureKey::TW2, 1, 4),
+    (FeatureKey::TW3, 2, 5),
+    (FeatureKey::TW4, 3, 6),
+];
+
+/// Resolve a [`FEATURE_WINDOWS`] entry to a `char` range for the boundary at `i`.
+pub fn feature_window(i: usize, len: usize, start: usize, end: usize) -> Range<usize> {
+    let clamp = |offset: usize| (i + offset).saturating_sub(3).min(len);
+    clamp(start)..clamp(end)
+}
@@ -0,0 +1,360 @@
+//! Streaming segmentation.
+//!
+//! [`Segmenter`](crate::stream::Segmenter) splits text that arrives in
+//! pieces, such as a corpus read from a file, without holding all of it in
+//! memory. The chunks are the same as those of
+//! [`Parser::parse`](crate::Parser::parse) on the concatenated text: a
+//! candidate boundary is only decided once the characters its feature
+//! windows, chunk length options and dictionary phrases can see have arrived.
+
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::Parser;
+use crate::dictionary::Dictionary;
+use crate::parser::chunk_limits;
+
+/// Number of characters the feature windows see on each side of a boundary.
+const CONTEXT: usize = 3;
+
+/// Incremental segmenter returned by [`Parser::segmenter`].
+///
+/// Feed text with [`push`](Self::push), then call [`finish`](Self::finish)
+/// at the end of the text. The chunks returned by all calls, in order, are
+/// those of [`Parser::parse`] on the whole text.
+#[derive(Clone, Debug)]
+pub struct Segmenter<'a> {
+    parser: &'a Parser,
+    /// Received characters that are still needed, starting at index `base`
+    /// of the text.
+    chars: Vec<char>,
+    base: usize,
+    /// Start of the chunk that has not been returned yet.
+    emitted: usize,
+    /// Boundaries before this index are final.
+    processed: usize,
+    /// Next candidate boundary to decide under the options.
+    next: usize,
+    /// Last boundary picked under the options, before the dictionary.
+    last: usize,
+    /// Boundaries picked under the options that are not final yet.
+    selected: Vec<usize>,
+}
+
+impl<'a> Segmenter<'a> {
+    const fn new(parser: &'a Parser) -> Self {
+        Self {
+            parser,
+            chars: Vec::new(),
+            base: 0,
+            emitted: 0,
+            processed: 1,
+            next: 1,
+            last: 0,
+            selected: Vec::new(),
+        }
+    }
+
+    /// Append `text` and return the chunks that are now complete.
+    #[must_use]
+    pub fn push(&mut self, text: &str) -> Vec<String> {
+        self.chars.extend(text.chars());
+        self.advance(false)
+    }
+
+    /// End the text and return the remaining chunks.
+    ///
+    /// The segmenter is reset, so it can be used for another text.
+    #[must_use]
+    pub fn finish(&mut self) -> Vec<String> {
+        let chunks = self.advance(true);
+        *self = Self::new(self.parser);
+        chunks
+    }
+
+    /// Number of characters received so far.
+    const fn len(&self) -> usize {
+        self.base + self.chars.len()
+    }
+
+    fn advance(&mut self, complete: bool) -> Vec<String> {
+        let len = self.len();
+        if len == 0 {
+            return Vec::new();
+        }
+        self.select(complete);
+
+        let dictionary = self.parser.dictionary();
+        let reach = dictionary.map_or(0, Dictionary::max_chars);
+        let safe = if complete {
+            len
+        } else {
+            // Under a maximum chunk length, a later candidate can still pick
+            // any position from `last + min` on.
+            let (min, max) = chunk_limits(self.parser.options());
+            let decided = if max == usize::MAX {
+                self.next
+            } else {
+                self.next.min(self.last + min)
+            };
+            decided.min(len.saturating_sub(reach))
+        };
+        let boundaries: Vec<usize> = match dictionary {
+            Some(dictionary) => {
+                // A phrase covering a boundary starts and ends within `reach`
+                // characters of it.
+                let start = self.processed.saturating_sub(reach);
+                let mut window: Vec<usize> = self
+                    .selected
+                    .iter()
+                    .filter(|&&boundary| boundary >= start)
+                    .map(|boundary| boundary - start)
+                    .collect();
+                dictionary.apply(&self.chars[start - self.base..], &mut window);
+                window
+                    .into_iter()
+                    .map(|boundary| boundary + start)
+                    .filter(|boundary| (self.processed..safe).contains(boundary))
+                    .collect()
+            }
+            None => self
+                .selected
+                .iter()
+                .copied()
+                .filter(|boundary| *boundary < safe)
+                .collect(),
+        };
+
+        let mut chunks = Vec::with_capacity(boundaries.len() + 1);
+        for boundary in boundaries {
+            chunks.push(self.slice(self.emitted, boundary));
+            self.emitted = boundary;
+        }
+        if complete && self.emitted < len {
+            chunks.push(self.slice(self.emitted, len));
+            self.emitted = len;
+        }
+
+        self.processed = self.processed.max(safe);
+        let processed = self.processed;
+        self.selected.retain(|boundary| *boundary >= processed);
+        let keep = self
+            .emitted
+            .min(self.last.saturating_sub(CONTEXT))
+            .min(self.processed.saturating_sub(reach));
+        self.chars.drain(..keep - self.base);
+        self.base = keep;
+        chunks
+    }
+
+    /// Pick boundaries under the options, as `select_boundaries` does on the
+    /// whole text, for every candidate that later text cannot change.
+    fn select(&mut self, complete: bool) {
+        let parser = self.parser;
+        let options = parser.options();
+        let (min, max) = chunk_limits(options);
+        let lookahead = min.max(CONTEXT);
+        let lookup = parser.compiled_model().lookup();
+        let len = self.len();
+        let score = |segmenter: &Self, i: usize| {
+            let index = i - segmenter.base;
+            let from = index.saturating_sub(CONTEXT);
+            let to = (index + CONTEXT).min(segmenter.chars.len());
+            parser.score(&lookup, &segmenter.chars[from..to], index - from)
+        };
+
+        // Until the end of the text, a candidate is only decided once `len`
+        // covers its feature windows and the minimum chunk after it. The
+        // length checks then give the same result as with the full length.
+        while self.next < len && (complete || len >= self.next + lookahead) {
+            let i = self.next;
+            self.next += 1;
+            if i - self.last < min {
+                continue;
+            }
+            if score(self, i) > options.threshold && len - i >= min {
+                self.selected.push(i);
+                self.last = i;
+            } else if i - self.last == max {
+                let start = self.last + min;
+                let end = if len - min >= start {
+                    i.min(len - min)
+                } else {
+                    i
+                };
+                let best = (start..=end)
+                    .map(|position| (position, score(self, position)))
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .map_or(i, |(position, _)| position);
+                self.selected.push(best);
+                self.last = best;
+            }
+        }
+    }
+
+    fn slice(&self, start: usize, end: usize) -> String {
+        self.chars[start - self.base..end - self.base]
+            .iter()
+            .collect()
+    }
+}
+
+/// Iterator over the chunks of text pieces, returned by [`Parser::parse_iter`].
+#[derive(Debug)]
+pub struct Chunks<'a, I> {
+    segmenter: Segmenter<'a>,
+    pieces: Option<I>,
+    pending: VecDeque<String>,
+}
+
+impl<I> Iterator for Chunks<'_, I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = self.pending.pop_front() {
+                return Some(chunk);
+            }
+            let pieces = self.pieces.as_mut()?;
+            if let Some(piece) = pieces.next() {
+                self.pending.extend(self.segmenter.push(piece.as_ref()));
+            } else {
+                self.pieces = None;
+                self.pending.extend(self.segmenter.finish());
+            }
+        }
+    }
+}
+
+impl Parser {
+    /// Create a [`Segmenter`] that splits text arriving in pieces.
+    #[must_use]
+    pub const fn segmenter(&self) -> Segmenter<'_> {
+        Segmenter::new(self)
+    }
+
+    /// Split the concatenation of `pieces` into chunks, lazily.
+    ///
+    /// The chunks are those of [`parse`](Self::parse) on the concatenated
+    /// text, so pieces can be split anywhere, e.g. `["今日は良", "い天気です"]`.
+    pub fn parse_iter<I>(&self, pieces: I) -> Chunks<'_, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Chunks {
+            segmenter: self.segmenter(),
+            pieces: Some(pieces.into_iter()),
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod read {
+    use std::collections::VecDeque;
+    use std::io::{self, ErrorKind, Read};
+    use std::str;
+
+    use super::Segmenter;
+    use crate::Parser;
+
+    const BUFFER_LEN: usize = 64 * 1024;
+
+    /// Iterator over the chunks of UTF-8 text read from a reader, returned by
+    /// [`Parser::parse_reader`].
+    #[derive(Debug)]
+    pub struct ReadChunks<'a, R> {
+        segmenter: Segmenter<'a>,
+        reader: Option<R>,
+        buffer: Vec<u8>,
+        /// Bytes at the end of the last read that start an incomplete `char`.
+        partial: usize,
+        pending: VecDeque<String>,
+    }
+
+    impl<R: Read> ReadChunks<'_, R> {
+        /// Read and segment the next block of text.
+        fn fill(&mut self) -> io::Result<()> {
+            let Some(reader) = self.reader.as_mut() else {
+                return Ok(());
+            };
+            let read = loop {
+                match reader.read(&mut self.buffer[self.partial..]) {
+                    Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                    result => break result?,
+                }
+            };
+            if read == 0 {
+                self.reader = None;
+                if self.partial > 0 {
+                    return Err(invalid_utf8());
+                }
+                self.pending.extend(self.segmenter.finish());
+                return Ok(());
+            }
+            let filled = self.partial + read;
+            let valid = match str::from_utf8(&self.buffer[..filled]) {
+                Ok(_) => filled,
+                Err(err) if err.error_len().is_none() => err.valid_up_to(),
+                Err(_) => {
+                    self.reader = None;
+                    return Err(invalid_utf8());
+                }
+            };
+            let text = str::from_utf8(&self.buffer[..valid]).map_err(|_| invalid_utf8())?;
+            self.pending.extend(self.segmenter.push(text));
+            self.buffer.copy_within(valid..filled, 0);
+            self.partial = filled - valid;
+            Ok(())
+        }
+    }
+
+    impl<R: Read> Iterator for ReadChunks<'_, R> {
+        type Item = io::Result<String>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                if let Some(chunk) = self.pending.pop_front() {
+                    return Some(Ok(chunk));
+                }
+                self.reader.as_ref()?;
+                if let Err(err) = self.fill() {
+                    self.reader = None;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+
+    fn invalid_utf8() -> io::Error {
+        io::Error::new(ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+    }
+
+    impl Parser {
+        /// Split UTF-8 text read from `reader` into chunks, lazily.
+        ///
+        /// The reader is read in blocks, so any [`Read`] works, including
+        /// [`BufRead`](std::io::BufRead) types such as a locked stdin. The
+        /// chunks are those of [`parse`](Self::parse) on the whole text. The
+        /// iterator yields an error and stops if reading fails or the text is
+        /// not valid UTF-8.
+        pub fn parse_reader<R: Read>(&self, reader: R) -> ReadChunks<'_, R> {
+            ReadChunks {
+                segmenter: self.segmenter(),
+                reader: Some(reader),
+                buffer: vec![0; BUFFER_LEN],
+                partial: 0,
+                pending: VecDeque::new(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use read::ReadChunks;
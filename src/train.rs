@@ -0,0 +1,170 @@
+//! `AdaBoost` training of `BudouX` models.
+//!
+//! This follows the upstream `BudouX` training script: every (feature key,
+//! n-gram) pair seen in the corpus is a binary weak learner, and each boosting
+//! round adds the learner with the lowest weighted error to the model.
+//! Samples come from the [`encode`](crate::encode) module, so the features
+//! match what [`Parser`](crate::Parser) scores, and a trained model can be
+//! loaded back with [`parse_model_json`](crate::model::parse_model_json).
+
+use std::collections::HashMap;
+
+use crate::encode::{DEFAULT_SEPARATOR, Sample, encode};
+use crate::model::{FeatureKey, Model};
+
+/// Factor applied to learner weights before rounding them to integers.
+const WEIGHT_SCALE: f64 = 1000.0;
+
+/// Options for [`train`].
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct TrainOptions {
+    /// Number of boosting rounds.
+    pub iterations: usize,
+    /// Character separating chunks in the corpus.
+    pub separator: char,
+}
+
+impl Default for TrainOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 10_000,
+            separator: DEFAULT_SEPARATOR,
+        }
+    }
+}
+
+/// Train a model from a segmented corpus.
+///
+/// Each line of `corpus` is one sentence whose chunks are separated by
+/// [`TrainOptions::separator`], e.g. `今日は▁良い▁天気です`. Empty lines are
+/// ignored.
+#[must_use]
+pub fn train(corpus: &str, options: Option<TrainOptions>) -> Model {
+    let options = options.unwrap_or_default();
+    let samples = encode(corpus, options.separator);
+    train_samples(samples, Some(options))
+}
+
+/// Train a model from already encoded samples.
+///
+/// [`TrainOptions::separator`] is not used.
+#[must_use]
+pub fn train_samples(
+    samples: impl IntoIterator<Item = Sample>,
+    options: Option<TrainOptions>,
+) -> Model {
+    let options = options.unwrap_or_default();
+    let dataset = Dataset::new(samples);
+    let alphas = dataset.boost(options.iterations);
+    dataset.into_model(&alphas)
+}
+
+/// Training samples with their features replaced by indices.
+struct Dataset {
+    features: Vec<(FeatureKey, String)>,
+    samples: Vec<Vec<usize>>,
+    labels: Vec<bool>,
+}
+
+impl Dataset {
+    fn new(samples: impl IntoIterator<Item = Sample>) -> Self {
+        let mut index: HashMap<(FeatureKey, String), usize> = HashMap::new();
+        let mut dataset = Self {
+            features: Vec::new(),
+            samples: Vec::new(),
+            labels: Vec::new(),
+        };
+        for sample in samples {
+            let features = sample
+                .features
+                .into_iter()
+                .map(|feature| {
+                    *index.entry(feature).or_insert_with_key(|key| {
+                        dataset.features.push(key.clone());
+                        dataset.features.len() - 1
+                    })
+                })
+                .collect();
+            dataset.samples.push(features);
+            dataset.labels.push(sample.is_boundary);
+        }
+        dataset
+    }
+
+    /// Run `AdaBoost` and return the accumulated weight of every feature.
+    fn boost(&self, iterations: usize) -> Vec<f64> {
+        let mut alphas = vec![0.0; self.features.len()];
+        if self.samples.is_empty() {
+            return alphas;
+        }
+        #[expect(clippy::cast_precision_loss)]
+        let mut weights = vec![1.0 / self.samples.len() as f64; self.samples.len()];
+        let mut positive = vec![0.0; self.features.len()];
+        let mut negative = vec![0.0; self.features.len()];
+
+        for _ in 0..iterations {
+            positive.fill(0.0);
+            negative.fill(0.0);
+            let mut positive_total = 0.0;
+            for ((sample, &label), &weight) in self.samples.iter().zip(&self.labels).zip(&weights) {
+                let sums = if label {
+                    positive_total += weight;
+                    &mut positive
+                } else {
+                    &mut negative
+                };
+                for &feature in sample {
+                    sums[feature] += weight;
+                }
+            }
+
+            // A learner predicts a boundary when its feature is present, so it
+            // errs on positives without the feature and negatives with it.
+            let Some((best, error)) = positive
+                .iter()
+                .zip(&negative)
+                .map(|(pos, neg)| positive_total - pos + neg)
+                .enumerate()
+                .max_by(|(_, a), (_, b)| (0.5 - a).abs().total_cmp(&(0.5 - b).abs()))
+            else {
+                break;
+            };
+            if (0.5 - error).abs() < f64::EPSILON {
+                break;
+            }
+            let error = error.clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+            let alpha = 0.5 * ((1.0 - error) / error).ln();
+            alphas[best] += alpha;
+
+            let mut total = 0.0;
+            for ((sample, &label), weight) in
+                self.samples.iter().zip(&self.labels).zip(&mut weights)
+            {
+                let prediction = sample.contains(&best);
+                *weight *= if prediction == label {
+                    (-alpha).exp()
+                } else {
+                    alpha.exp()
+                };
+                total += *weight;
+            }
+            for weight in &mut weights {
+                *weight /= total;
+            }
+        }
+        alphas
+    }
+
+    fn into_model(self, alphas: &[f64]) -> Model {
+        let mut model = Model::new();
+        for ((feature, ngram), alpha) in self.features.into_iter().zip(alphas) {
+            #[expect(clippy::cast_possible_truncation)]
+            let weight = (alpha * WEIGHT_SCALE).round() as i32;
+            if weight != 0 {
+                model.entry(feature).or_default().insert(ngram, weight);
+            }
+        }
+        model
+    }
+}
@@ -8,15 +8,20 @@ use alloc::vec::Vec;
 use wasm_bindgen::prelude::*;
 
 use crate::Parser;
-use crate::model::{
-    load_default_japanese_parser, load_default_simplified_chinese_parser, load_default_thai_parser,
-    load_default_traditional_chinese_parser,
-};
+#[cfg(feature = "model-ja")]
+use crate::model::load_default_japanese_parser;
+#[cfg(feature = "model-zh-hans")]
+use crate::model::load_default_simplified_chinese_parser;
+#[cfg(feature = "model-th")]
+use crate::model::load_default_thai_parser;
+#[cfg(feature = "model-zh-hant")]
+use crate::model::load_default_traditional_chinese_parser;
 
 /// `BudouY` parser for JavaScript.
 ///
 /// Use the static methods to create a parser for a specific language,
-/// then call `parse()` to split text into semantic chunks.
+/// then call `parse()` to split text into semantic chunks. Only languages
+/// whose `model-*` feature is enabled have a constructor.
 #[derive(Debug)]
 #[wasm_bindgen(js_name = BudouY)]
 pub struct JsParser {
@@ -26,6 +31,7 @@ pub struct JsParser {
 #[wasm_bindgen(js_class = BudouY)]
 impl JsParser {
     /// Create a parser for Japanese text.
+    #[cfg(feature = "model-ja")]
     #[wasm_bindgen(js_name = japanese)]
     pub fn japanese() -> Self {
         Self {
@@ -34,6 +40,7 @@ impl JsParser {
     }
 
     /// Create a parser for Simplified Chinese text.
+    #[cfg(feature = "model-zh-hans")]
     #[wasm_bindgen(js_name = simplifiedChinese)]
     pub fn simplified_chinese() -> Self {
         Self {
@@ -42,6 +49,7 @@ impl JsParser {
     }
 
     /// Create a parser for Traditional Chinese text.
+    #[cfg(feature = "model-zh-hant")]
     #[wasm_bindgen(js_name = traditionalChinese)]
     pub fn traditional_chinese() -> Self {
         Self {
@@ -50,6 +58,7 @@ impl JsParser {
     }
 
     /// Create a parser for Thai text.
+    #[cfg(feature = "model-th")]
     #[wasm_bindgen(js_name = thai)]
     pub fn thai() -> Self {
         Self {
@@ -0,0 +1,113 @@
+//! Width-aware line wrapping at semantic boundaries.
+//!
+//! Lines are only broken at boundaries reported by a [`Parser`]. A chunk that
+//! is wider than a whole line on its own is broken between grapheme clusters.
+//! Widths follow Unicode East Asian Width, so full-width characters take two
+//! columns.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::Parser;
+
+/// Display width of `text` in terminal columns.
+#[must_use]
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+/// Wrap `text` into lines no wider than `width` columns.
+///
+/// Existing line breaks in `text` are kept. Whitespace at the end of a line
+/// and at the start of the following line is dropped at every inserted break.
+/// A single grapheme cluster wider than `width` is placed on a line of its own.
+#[must_use]
+pub fn wrap<'a>(parser: &Parser, text: &'a str, width: usize) -> Vec<&'a str> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut wrapper = LineWrapper {
+            text: paragraph,
+            width,
+            line: 0..0,
+            line_width: 0,
+            lines: &mut lines,
+        };
+        let first = wrapper.lines.len();
+        for span in parser.parse_spans(paragraph) {
+            wrapper.push_chunk(span);
+        }
+        wrapper.break_line();
+        if lines.len() == first {
+            lines.push("");
+        }
+    }
+    lines
+}
+
+struct LineWrapper<'a, 'b> {
+    text: &'a str,
+    width: usize,
+    line: Range<usize>,
+    line_width: usize,
+    lines: &'b mut Vec<&'a str>,
+}
+
+impl LineWrapper<'_, '_> {
+    fn push_chunk(&mut self, span: Range<usize>) {
+        let chunk = &self.text[span.clone()];
+        let visible = display_width(chunk.trim_end());
+        if !self.line.is_empty() && self.line_width + visible <= self.width {
+            self.extend(span.end, display_width(chunk));
+            return;
+        }
+        if !self.line.is_empty() {
+            self.break_line();
+        }
+        // Only a line that follows a break starts after the paragraph start,
+        // so indentation at the start of the paragraph is kept.
+        let start = if self.line.start == 0 {
+            span.start
+        } else {
+            span.end - chunk.trim_start().len()
+        };
+        self.line = start..start;
+        if display_width(self.text[start..span.end].trim_end()) <= self.width {
+            self.extend(span.end, display_width(&self.text[start..span.end]));
+            return;
+        }
+        for (offset, grapheme) in self.text[start..span.end].grapheme_indices(true) {
+            let end = start + offset + grapheme.len();
+            let grapheme_width = display_width(grapheme);
+            if !self.line.is_empty() && self.line_width + grapheme_width > self.width {
+                self.break_line();
+                self.line = start + offset..start + offset;
+            }
+            // As above, whitespace is dropped at the start of a line that
+            // follows a break.
+            if self.line.is_empty() && self.line.start != 0 && grapheme.trim().is_empty() {
+                self.line = end..end;
+                continue;
+            }
+            self.extend(end, grapheme_width);
+        }
+    }
+
+    const fn extend(&mut self, end: usize, width: usize) {
+        self.line.end = end;
+        self.line_width += width;
+    }
+
+    /// End the current line. Whitespace alone does not make a line.
+    fn break_line(&mut self) {
+        let line = self.text[self.line.clone()].trim_end();
+        if !line.is_empty() {
+            self.lines.push(line);
+        }
+        self.line = self.line.end..self.line.end;
+        self.line_width = 0;
+    }
+}
@@ -0,0 +1,28 @@
+//! Parallel batch parsing tests.
+
+#![cfg(feature = "rayon")]
+
+use budouy::dictionary::{Dictionary, Rule};
+use budouy::model::FeatureKey;
+use budouy::{Model, Parser};
+
+#[test]
+fn batch_matches_sequential_parsing_in_order() {
+    let mut model = Model::new();
+    model.insert(FeatureKey::UW4, [("a".to_string(), 10_000)].into());
+    let mut dictionary = Dictionary::new();
+    dictionary.insert("cd", Rule::Break);
+    let parser = Parser::new(model).with_dictionary(dictionary);
+
+    let inputs: Vec<String> = (0..1000)
+        .map(|i| "abcde".repeat(i % 7) + &i.to_string())
+        .collect();
+    let expected: Vec<Vec<String>> = inputs.iter().map(|input| parser.parse(input)).collect();
+    assert_eq!(parser.parse_batch(&inputs), expected);
+
+    assert_eq!(
+        parser.parse_batch(&["abcdeabcd", ""]),
+        vec![vec!["ab", "cd", "e", "ab", "cd"], vec![]]
+    );
+    assert!(parser.parse_batch::<&str>(&[]).is_empty());
+}
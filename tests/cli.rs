@@ -0,0 +1,36 @@
+//! CLI tests.
+
+#![cfg(all(feature = "cli", feature = "model-ja"))]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Run `budouy` with `args` and `input` on stdin, and return its stdout.
+fn budouy(args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_budouy"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("spawn budouy");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(input.as_bytes())
+        .expect("write stdin");
+    let output = child.wait_with_output().expect("wait for budouy");
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+#[test]
+fn html_keeps_documents_that_start_with_a_comment() {
+    let html = "\u{FEFF}<!-- generated -->\n<!DOCTYPE html>\n<html lang=\"ja\"><head><title>今日は良い天気です</title></head>\
+                <body><p>今日は良い天気です</p></body></html>\n";
+    assert_eq!(
+        budouy(&["html", "--lang", "ja", "--separator", "|"], html),
+        "\u{FEFF}<!-- generated -->\n<!DOCTYPE html>\n<html lang=\"ja\"><head><title>今日は良い天気です</title></head>\
+         <body><p style=\"word-break: keep-all; overflow-wrap: anywhere;\">今日は|良い|天気です</p></body></html>\n",
+    );
+}
@@ -0,0 +1,26 @@
+//! Helpers shared by the integration tests.
+//!
+//! Test crates declare this module `pub`, so the helpers a crate does not use
+//! are not reported as dead code.
+
+use budouy::model::FeatureKey;
+use budouy::{Model, Parser};
+
+/// Model with the given `(feature, n-gram, weight)` entries.
+#[must_use]
+pub fn model(entries: &[(FeatureKey, &str, i32)]) -> Model {
+    let mut model = Model::new();
+    for &(feature, ngram, weight) in entries {
+        model
+            .entry(feature)
+            .or_default()
+            .insert(ngram.to_string(), weight);
+    }
+    model
+}
+
+/// Parser that breaks before every `ch`.
+#[must_use]
+pub fn parser_breaking_before(ch: &str) -> Parser {
+    Parser::new(model(&[(FeatureKey::UW4, ch, 10_000)]))
+}
@@ -0,0 +1,93 @@
+//! User dictionary tests.
+
+use budouy::dictionary::{Dictionary, DictionaryError, Rule};
+use budouy::model::FeatureKey;
+use budouy::{Model, Parser, ParserOptions};
+
+fn parser() -> Parser {
+    let mut model = Model::new();
+    model.insert(FeatureKey::UW4, [("a".to_string(), 10_000)].into());
+    Parser::new(model)
+}
+
+fn dictionary(entries: &[(&str, Rule)]) -> Dictionary {
+    let mut dictionary = Dictionary::new();
+    for &(phrase, rule) in entries {
+        dictionary.insert(phrase, rule);
+    }
+    dictionary
+}
+
+#[test]
+fn dictionary_keeps_and_breaks_phrases() {
+    assert_eq!(parser().parse("abcdeabcd"), vec!["abcde", "abcd"]);
+
+    let keep = parser().with_dictionary(dictionary(&[("eab", Rule::Keep)]));
+    assert_eq!(keep.parse("abcdeabcd"), vec!["abcdeabcd"]);
+
+    let split = parser().with_dictionary(dictionary(&[("cd", Rule::Break)]));
+    assert_eq!(split.parse("abcdeabcd"), vec!["ab", "cd", "e", "ab", "cd"]);
+    assert_eq!(split.parse_boundaries_bytes("abcdeabcd"), vec![2, 4, 5, 7]);
+
+    let both = parser().with_dictionary(dictionary(&[("cd", Rule::Break), ("bcde", Rule::Keep)]));
+    assert_eq!(both.parse("abcdeabcd"), vec!["abcde", "ab", "cd"]);
+}
+
+#[test]
+fn dictionary_overrides_chunk_limits() {
+    let mut options = ParserOptions::default();
+    options.max_chunk_chars = Some(3);
+    let parser = parser()
+        .with_options(options)
+        .with_dictionary(dictionary(&[("bcdeab", Rule::Keep)]));
+    assert_eq!(parser.parse("abcdeabcd"), vec!["abcdeabc", "d"]);
+}
+
+#[test]
+fn dictionary_parses_tsv_and_json() {
+    let tsv = "# brand names\nGoogle アシスタント\n\nです\tbreak\n天気\tkeep\n";
+    let dictionary = Dictionary::parse_tsv(tsv).expect("valid tsv");
+    assert_eq!(dictionary.len(), 3);
+    assert_eq!(dictionary.get("Google アシスタント"), Some(Rule::Keep));
+    assert_eq!(dictionary.get("です"), Some(Rule::Break));
+    assert_eq!(dictionary.get("天"), None);
+
+    let json = r#"{"keep": ["Google アシスタント", "天気"], "break": ["です"]}"#;
+    assert_eq!(
+        Dictionary::parse_json(json).expect("valid json"),
+        dictionary
+    );
+
+    assert!(matches!(
+        Dictionary::parse_tsv("a\n\tkeep\n"),
+        Err(DictionaryError::EmptyPhrase { line: 2 })
+    ));
+    assert!(matches!(
+        Dictionary::parse_tsv("a\tsplit\n"),
+        Err(DictionaryError::UnknownRule { line: 1, .. })
+    ));
+    assert!(matches!(
+        Dictionary::parse_json(r#"{"never": ["a"]}"#),
+        Err(DictionaryError::Json(_))
+    ));
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn html_processor_applies_dictionary_across_elements() {
+    use budouy::HTMLProcessingParser;
+
+    let html = "abc<b>de</b>abcd";
+    let plain = HTMLProcessingParser::new(parser(), None);
+    assert_eq!(
+        plain.translate_html_string(html),
+        "<span style=\"word-break: keep-all; overflow-wrap: anywhere;\">abc<b>de</b>\u{200B}abcd</span>"
+    );
+
+    let keep = parser().with_dictionary(dictionary(&[("eab", Rule::Keep)]));
+    let html_parser = HTMLProcessingParser::new(keep, None);
+    assert_eq!(
+        html_parser.translate_html_string(html),
+        "<span>abc<b>de</b>abcd</span>",
+    );
+}
@@ -0,0 +1,68 @@
+//! Model diff tests.
+
+use budouy::eval::compare;
+use budouy::model::{FeatureKey, diff_models};
+use budouy::{Model, Parser};
+
+pub mod common;
+
+use common::model;
+
+fn old() -> Model {
+    model(&[
+        (FeatureKey::UW4, "a", 1000),
+        (FeatureKey::UW4, "b", 10),
+        (FeatureKey::BW2, "ab", -5),
+    ])
+}
+
+fn new() -> Model {
+    model(&[
+        (FeatureKey::UW4, "a", 1000),
+        (FeatureKey::UW4, "b", 2000),
+        (FeatureKey::UW4, "c", 7),
+        (FeatureKey::TW1, "abc", 1),
+    ])
+}
+
+#[test]
+fn diff_lists_added_removed_and_changed_entries() {
+    let diff = diff_models(&old(), &new());
+    let features: Vec<FeatureKey> = diff.features.iter().map(|f| f.feature).collect();
+    assert_eq!(
+        features,
+        vec![FeatureKey::UW4, FeatureKey::BW2, FeatureKey::TW1]
+    );
+
+    let uw4 = &diff.features[0];
+    assert_eq!(uw4.added.len(), 1);
+    assert_eq!((uw4.added[0].ngram.as_str(), uw4.added[0].weight), ("c", 7));
+    assert!(uw4.removed.is_empty());
+    assert_eq!(uw4.changed.len(), 1);
+    let change = &uw4.changed[0];
+    assert_eq!(
+        (change.ngram.as_str(), change.old, change.new),
+        ("b", 10, 2000)
+    );
+
+    let bw2 = &diff.features[1];
+    assert!(bw2.added.is_empty());
+    assert_eq!(
+        (bw2.removed[0].ngram.as_str(), bw2.removed[0].weight),
+        ("ab", -5)
+    );
+
+    assert!(diff_models(&old(), &old()).is_empty());
+}
+
+#[test]
+fn diff_reports_changed_segmentations() {
+    let corpus = "abcab\nxyz\n";
+    let evaluation = compare(&Parser::new(old()), &Parser::new(new()), corpus);
+    assert_eq!(evaluation.metrics.sentences, 2);
+    assert_eq!(evaluation.diffs.len(), 1);
+    let diff = &evaluation.diffs[0];
+    assert_eq!(diff.sentence, "abcab");
+    assert_eq!(diff.gold, vec![3]);
+    assert_eq!(diff.predicted, vec![1, 4]);
+}
@@ -0,0 +1,59 @@
+//! Training data encoding tests.
+
+use budouy::Parser;
+use budouy::encode::{DEFAULT_SEPARATOR, EncodeError, encode, encode_sentence, parse_encoded};
+use budouy::model::FeatureKey;
+
+#[test]
+fn encode_writes_upstream_format() {
+    let samples = encode("ab▁cd", DEFAULT_SEPARATOR);
+    let lines: Vec<String> = samples.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "-1\tUW3:a\tUW4:b\tUW5:c\tUW6:d\tBW1:a\tBW2:ab\tBW3:bc\tTW1:a\tTW2:ab\tTW3:abc\tTW4:bcd",
+            "1\tUW2:a\tUW3:b\tUW4:c\tUW5:d\tBW1:ab\tBW2:bc\tBW3:cd\tTW1:ab\tTW2:abc\tTW3:bcd\tTW4:cd",
+            "-1\tUW1:a\tUW2:b\tUW3:c\tUW4:d\tBW1:bc\tBW2:cd\tBW3:d\tTW1:abc\tTW2:bcd\tTW3:cd\tTW4:d",
+        ]
+    );
+    assert_eq!(samples, encode_sentence("abcd", &[2]));
+}
+
+#[test]
+fn encoded_data_round_trips() {
+    let samples = encode("今日は▁良い▁天気です\n\nab▁c", DEFAULT_SEPARATOR);
+    let data = samples
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert_eq!(parse_encoded(&data).expect("parse"), samples);
+}
+
+#[test]
+fn parse_encoded_rejects_bad_lines() {
+    assert!(matches!(
+        parse_encoded("1\tUW1:a\n0\tUW1:b"),
+        Err(EncodeError::InvalidLabel { line: 2, .. })
+    ));
+    assert!(matches!(
+        parse_encoded("1\tXX1:a"),
+        Err(EncodeError::InvalidFeature { line: 1, .. })
+    ));
+}
+
+#[test]
+fn encode_matches_parser_feature_windows() {
+    let parser = Parser::new(budouy::Model::new());
+    let sentence = "Google の使命";
+    let samples = encode_sentence(sentence, &[]);
+    for (sample, explanation) in samples.iter().zip(parser.explain(sentence)) {
+        let explained: Vec<(FeatureKey, String)> = explanation
+            .contributions
+            .into_iter()
+            .filter(|contribution| !contribution.ngram.is_empty())
+            .map(|contribution| (contribution.feature, contribution.ngram))
+            .collect();
+        assert_eq!(sample.features, explained);
+    }
+}
@@ -0,0 +1,46 @@
+//! Evaluation tests.
+
+use std::collections::HashMap;
+
+use budouy::eval::evaluate;
+use budouy::model::FeatureKey;
+use budouy::{Model, Parser};
+
+fn parser() -> Parser {
+    let mut model: Model = HashMap::new();
+    model.insert(FeatureKey::UW4, HashMap::from([("b".to_string(), 10_000)]));
+    Parser::new(model)
+}
+
+#[test]
+fn evaluate_counts_boundary_agreement() {
+    // The parser breaks before every "b": "a|bc|bc" and "a|bcd".
+    let evaluation = evaluate(&parser(), "a▁bc▁bc\n\na▁b▁cd\n", '▁');
+    let metrics = &evaluation.metrics;
+    assert_eq!(metrics.sentences, 2);
+    assert_eq!(metrics.true_positives, 3);
+    assert_eq!(metrics.false_positives, 0);
+    assert_eq!(metrics.false_negatives, 1);
+    assert_eq!(metrics.true_negatives, 3);
+    assert!((metrics.accuracy - 6.0 / 7.0).abs() < 1e-9);
+    assert!((metrics.precision - 1.0).abs() < 1e-9);
+    assert!((metrics.recall - 0.75).abs() < 1e-9);
+    assert!((metrics.f1 - 6.0 / 7.0).abs() < 1e-9);
+
+    assert_eq!(evaluation.diffs.len(), 1);
+    let diff = &evaluation.diffs[0];
+    assert_eq!(diff.line, 3);
+    assert_eq!(diff.sentence, "abcd");
+    assert_eq!(diff.gold, vec![1, 2]);
+    assert_eq!(diff.predicted, vec![1]);
+    assert_eq!(diff.missing, vec![2]);
+    assert!(diff.extra.is_empty());
+}
+
+#[test]
+fn evaluate_empty_gold_reports_zero_rates() {
+    let evaluation = evaluate(&parser(), "", '▁');
+    assert_eq!(evaluation.metrics.sentences, 0);
+    assert!(evaluation.metrics.f1.abs() < f64::EPSILON);
+    assert!(evaluation.diffs.is_empty());
+}
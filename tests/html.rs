@@ -0,0 +1,207 @@
+//! HTML processing tests.
+
+#![cfg(feature = "html")]
+
+use budouy::{
+    HTMLProcessingParser, HTMLProcessor, HTMLProcessorOptions, MultilingualHTMLProcessor, Parser,
+    Separator,
+};
+
+pub mod common;
+
+use common::parser_breaking_before;
+
+fn parser() -> Parser {
+    parser_breaking_before("a")
+}
+
+fn multilingual(default_lang: Option<&str>) -> MultilingualHTMLProcessor {
+    let mut options = HTMLProcessorOptions::default();
+    options.separator = Some(Separator::Text("|".to_string()));
+    options.class_name = Some("budoux".to_string());
+    options.lang = default_lang.map(str::to_string);
+    MultilingualHTMLProcessor::new(
+        [
+            ("ja", parser_breaking_before("a")),
+            ("zh-Hans", parser_breaking_before("b")),
+            ("zh-hant", parser_breaking_before("c")),
+        ],
+        Some(options),
+    )
+}
+
+#[test]
+fn inline_text_joins_paragraph_and_br_starts_a_new_one() {
+    let html_parser = HTMLProcessingParser::new(parser(), None);
+    assert_eq!(
+        html_parser.translate_html_string("<p>xya<i>bca</i>bc</p>"),
+        "<p style=\"word-break: keep-all; overflow-wrap: anywhere;\">xy\u{200B}a<i>bc\u{200B}a</i>bc</p>",
+    );
+    assert_eq!(
+        html_parser.translate_html_string("<p>xyab<br>abc<b>ab</b></p>"),
+        "<p style=\"word-break: keep-all; overflow-wrap: anywhere;\">xy\u{200B}ab<br>abc<b>\u{200B}ab</b></p>",
+    );
+    assert_eq!(
+        html_parser.translate_html_string("xy<br>abc<br>ab"),
+        "<span>xy<br>abc<br>ab</span>",
+    );
+}
+
+#[test]
+fn element_separator_is_inserted_in_place() {
+    let mut options = HTMLProcessorOptions::default();
+    options.class_name = Some("budoux".to_string());
+    options.separator = Some(Separator::element("wbr"));
+    let processor = HTMLProcessor::new(parser(), Some(options));
+    assert_eq!(
+        processor.apply_to_html_string("<p>xyabcd<b>eabc</b></p>"),
+        "<p class=\"budoux\">xy<wbr>abcd<b>e<wbr>abc</b></p>",
+    );
+}
+
+#[test]
+fn document_keeps_doctype_head_and_attributes() {
+    let processor = HTMLProcessor::new(parser(), None);
+    let html = "<!DOCTYPE html><html lang=\"ja\" class=\"page\"><head><title>xyabc</title>\
+                <meta charset=\"utf-8\"></head><body id=\"top\"><p>xyabc</p></body></html>";
+    assert_eq!(
+        processor.apply_to_document(html),
+        "<!DOCTYPE html><html lang=\"ja\" class=\"page\"><head><title>xyabc</title>\
+         <meta charset=\"utf-8\"></head><body id=\"top\"><p style=\"word-break: keep-all; \
+         overflow-wrap: anywhere;\">xy\u{200B}abc</p></body></html>",
+    );
+    assert_eq!(processor.apply_to_document(""), "");
+    assert_eq!(processor.apply_to_document("  \n"), "  \n");
+    assert_eq!(
+        processor.apply_to_document("<!DOCTYPE html>\n"),
+        "<!DOCTYPE html>\n<html><head></head><body></body></html>",
+    );
+
+    let html = "<!doctype html>\n<html>\n<head></head>\n<body>\n<p>xyabc</p>\n</body>\n</html>\n";
+    assert_eq!(
+        processor.apply_to_document(html),
+        "<!doctype html>\n<html><head></head>\n<body>\n<p style=\"word-break: keep-all; \
+         overflow-wrap: anywhere;\">xy\u{200B}abc</p>\n\n</body></html>\n",
+    );
+}
+
+#[test]
+fn lang_attributes_select_processed_text() {
+    let mut options = HTMLProcessorOptions::default();
+    options.class_name = Some("budoux".to_string());
+    options.separator = Some(Separator::Text("|".to_string()));
+    options.lang = Some("ja".to_string());
+    let processor = HTMLProcessor::new(parser(), Some(options));
+    let html = "<!DOCTYPE html><html lang=\"ja-JP\"><head></head><body>\
+                <p>xyabc<span lang=\"en\">xyabc</span>xa</p>\
+                <p xml:lang=\"en\">xyabc</p><p lang=\"JA\">xyabc</p><p lang=\"\">xyabc</p>\
+                </body></html>";
+    assert_eq!(
+        processor.apply_to_document(html),
+        "<!DOCTYPE html><html lang=\"ja-JP\"><head></head><body>\
+         <p class=\"budoux\">xy|abc<span lang=\"en\">xyabc</span>x|a</p>\
+         <p xml:lang=\"en\">xyabc</p><p lang=\"JA\" class=\"budoux\">xy|abc</p><p lang=\"\">xyabc</p>\
+         </body></html>",
+    );
+
+    let english = "<html lang=\"en\"><head></head><body><p>xyabc</p></body></html>";
+    assert_eq!(processor.apply_to_document(english), english);
+}
+
+#[test]
+fn multilingual_parser_fallbacks() {
+    let processor = multilingual(None);
+    let parsed = |lang: &str| {
+        processor
+            .parser_for(lang)
+            .map(|parser| parser.parse("xaxbxc"))
+    };
+    let ja = Some(vec!["x".to_string(), "axbxc".to_string()]);
+    let simplified = Some(vec!["xax".to_string(), "bxc".to_string()]);
+    let traditional = Some(vec!["xaxbx".to_string(), "c".to_string()]);
+    for (lang, expected) in [
+        ("ja", &ja),
+        ("JA-jp", &ja),
+        ("ja_JP", &ja),
+        ("zh", &simplified),
+        ("zh-CN", &simplified),
+        ("zh-Hans-TW", &simplified),
+        ("cmn-SG", &simplified),
+        ("zh-Hant", &traditional),
+        ("zh-TW", &traditional),
+        ("zh-HK", &traditional),
+        ("yue", &traditional),
+        ("en", &None),
+        ("jav", &None),
+        ("", &None),
+    ] {
+        assert_eq!(&parsed(lang), expected, "{lang}");
+    }
+}
+
+#[test]
+fn multilingual_processor_picks_parser_per_block() {
+    let html = "<!DOCTYPE html><html lang=\"ja\"><head></head><body>\
+                <p>xaxbxc<span lang=\"zh-TW\">xaxbxc</span></p>\
+                <div lang=\"zh\"><p>xaxbxc</p><p lang=\"en\">xaxbxc</p></div>\
+                </body></html>";
+    assert_eq!(
+        multilingual(None).apply_to_document(html),
+        "<!DOCTYPE html><html lang=\"ja\"><head></head><body>\
+         <p class=\"budoux\">x|axbxc<span lang=\"zh-TW\" class=\"budoux\">xaxbx|c</span></p>\
+         <div lang=\"zh\"><p class=\"budoux\">xax|bxc</p><p lang=\"en\">xaxbxc</p></div>\
+         </body></html>",
+    );
+
+    let fragment = "<p>xaxbxc</p>";
+    assert_eq!(multilingual(None).apply_to_html_string(fragment), fragment);
+    assert_eq!(
+        multilingual(Some("zh-Hant")).apply_to_html_string(fragment),
+        "<p class=\"budoux\">xaxbx|c</p>",
+    );
+}
+
+#[cfg(all(feature = "model-ja", feature = "model-zh-hans"))]
+#[test]
+fn multilingual_processor_uses_default_parsers() {
+    use budouy::model::{load_default_japanese_parser, load_default_simplified_chinese_parser};
+
+    let mut options = HTMLProcessorOptions::default();
+    options.separator = Some(Separator::Text("|".to_string()));
+    let processor = MultilingualHTMLProcessor::with_default_parsers(Some(options));
+    let ja = load_default_japanese_parser()
+        .parse("今日は良い天気です")
+        .join("|");
+    let zh = load_default_simplified_chinese_parser()
+        .parse("今天天气很好")
+        .join("|");
+    let output = processor.apply_to_html_string(
+        "<div><p lang=\"ja\">今日は良い天気です</p><p lang=\"zh-CN\">今天天气很好</p>\
+         <p lang=\"en\">Nice weather</p></div>",
+    );
+    assert!(output.contains(&format!(">{ja}</p>")), "{output}");
+    assert!(output.contains(&format!(">{zh}</p>")), "{output}");
+    assert!(
+        output.contains("<p lang=\"en\">Nice weather</p>"),
+        "{output}"
+    );
+}
+
+#[test]
+fn inline_lang_keeps_paragraph_unless_language_changes() {
+    // Without a language to filter by, `lang` attributes change nothing.
+    let processor = HTMLProcessor::new(parser(), None);
+    assert_eq!(
+        processor.apply_to_html_string("<p>xy<span lang=\"ja\">abc</span>xa</p>"),
+        "<p style=\"word-break: keep-all; overflow-wrap: anywhere;\">xy<span lang=\"ja\">\u{200B}abc</span>x\u{200B}a</p>",
+    );
+
+    // Language tags are compared case-insensitively.
+    let mut options = HTMLProcessorOptions::default();
+    options.lang = Some("ja".to_string());
+    let processor = HTMLProcessor::new(parser(), Some(options));
+    assert_eq!(
+        processor.apply_to_html_string("<p lang=\"ja\">xy<span lang=\"JA\">abc</span>xa</p>"),
+        "<p lang=\"ja\" style=\"word-break: keep-all; overflow-wrap: anywhere;\">xy<span lang=\"JA\">\u{200B}abc</span>x\u{200B}a</p>",
+    );
+}
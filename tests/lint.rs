@@ -0,0 +1,101 @@
+//! Model lint tests.
+
+use budouy::model::{FeatureKey, LintKind, Severity, lint};
+
+pub mod common;
+
+use common::model;
+
+#[test]
+fn lint_accepts_well_formed_model() {
+    let model = model(&[
+        (FeatureKey::UW4, "a", 10),
+        (FeatureKey::BW2, "ab", -3),
+        (FeatureKey::TW3, "abc", 7),
+    ]);
+    assert!(lint(&model).is_empty());
+}
+
+#[test]
+fn lint_reports_ngram_arity() {
+    let model = model(&[
+        (FeatureKey::UW1, "", 1),
+        (FeatureKey::UW1, "abc", 1),
+        (FeatureKey::BW2, "a", 1),
+        (FeatureKey::TW1, "ab", 1),
+    ]);
+    let issues: Vec<(Severity, LintKind)> = lint(&model)
+        .into_iter()
+        .map(|issue| (issue.severity, issue.kind))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            // Empty windows, e.g. UW1 before the third character, are never
+            // looked up.
+            (
+                Severity::Error,
+                LintKind::Unreachable {
+                    feature: FeatureKey::UW1,
+                    ngram: String::new(),
+                }
+            ),
+            (
+                Severity::Error,
+                LintKind::Unreachable {
+                    feature: FeatureKey::UW1,
+                    ngram: "abc".to_string(),
+                }
+            ),
+            (
+                Severity::Error,
+                LintKind::Unreachable {
+                    feature: FeatureKey::BW2,
+                    ngram: "a".to_string(),
+                }
+            ),
+            (
+                Severity::Warning,
+                LintKind::EdgeOnly {
+                    feature: FeatureKey::TW1,
+                    ngram: "ab".to_string(),
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn lint_reports_suspicious_entries() {
+    let model = model(&[
+        (FeatureKey::UW4, "a", 0),
+        (FeatureKey::BW2, "ab", i32::MAX),
+        (FeatureKey::BW2, "ＡＢ", 1),
+    ]);
+    let kinds: Vec<LintKind> = lint(&model).into_iter().map(|issue| issue.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            LintKind::ZeroWeight {
+                feature: FeatureKey::UW4,
+                ngram: "a".to_string(),
+            },
+            LintKind::DuplicateAfterNormalization {
+                feature: FeatureKey::BW2,
+                ngrams: vec!["ab".to_string(), "ＡＢ".to_string()],
+            },
+            LintKind::BaseScoreOverflow {
+                total: i64::from(i32::MAX) + 1,
+            },
+        ]
+    );
+}
+
+#[cfg(feature = "model-ja")]
+#[test]
+fn vendored_japanese_model_is_clean() {
+    use budouy::model::load_default_japanese_parser;
+
+    let model = load_default_japanese_parser().compiled_model().to_model();
+    assert!(lint(&model).is_empty());
+}
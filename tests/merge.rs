@@ -0,0 +1,95 @@
+//! Model merging tests.
+
+use budouy::model::{FeatureKey, MergeStrategy, merge_models};
+use budouy::{Model, Parser, ParserOptions};
+
+pub mod common;
+
+use common::model;
+
+fn base() -> Model {
+    model(&[
+        (FeatureKey::UW4, "a", 100),
+        (FeatureKey::UW4, "b", -40),
+        (FeatureKey::BW2, "ab", 5),
+    ])
+}
+
+fn other() -> Model {
+    model(&[
+        (FeatureKey::UW4, "a", 20),
+        (FeatureKey::UW4, "c", 7),
+        (FeatureKey::BW2, "ab", -5),
+    ])
+}
+
+#[test]
+fn sum_adds_weights_and_drops_zeros() {
+    assert_eq!(
+        merge_models(&base(), &other(), MergeStrategy::Sum),
+        model(&[
+            (FeatureKey::UW4, "a", 120),
+            (FeatureKey::UW4, "b", -40),
+            (FeatureKey::UW4, "c", 7),
+        ])
+    );
+    let saturated = merge_models(
+        &model(&[(FeatureKey::UW1, "x", i32::MAX)]),
+        &model(&[(FeatureKey::UW1, "x", 1)]),
+        MergeStrategy::Sum,
+    );
+    assert_eq!(saturated, model(&[(FeatureKey::UW1, "x", i32::MAX)]));
+}
+
+#[test]
+fn override_prefers_other_model() {
+    assert_eq!(
+        merge_models(&base(), &other(), MergeStrategy::Override),
+        model(&[
+            (FeatureKey::UW4, "a", 20),
+            (FeatureKey::UW4, "b", -40),
+            (FeatureKey::UW4, "c", 7),
+            (FeatureKey::BW2, "ab", -5),
+        ])
+    );
+}
+
+#[test]
+fn interpolate_rounds_half_away_from_zero() {
+    let merged = merge_models(
+        &base(),
+        &other(),
+        MergeStrategy::Interpolate { weight: 0.5 },
+    );
+    assert_eq!(
+        merged,
+        model(&[
+            (FeatureKey::UW4, "a", 60),
+            (FeatureKey::UW4, "b", -20),
+            (FeatureKey::UW4, "c", 4),
+        ])
+    );
+    assert_eq!(
+        merge_models(
+            &base(),
+            &other(),
+            MergeStrategy::Interpolate { weight: 0.0 }
+        ),
+        base()
+    );
+}
+
+#[test]
+fn overrides_change_parse_and_keep_options() {
+    let mut options = ParserOptions::default();
+    options.min_chunk_chars = Some(2);
+    let parser = Parser::new(model(&[(FeatureKey::UW4, "a", 10_000)])).with_options(options);
+    assert_eq!(parser.parse("abcdeabcd"), vec!["abcde", "abcd"]);
+
+    let overridden = parser.with_overrides(&model(&[
+        (FeatureKey::UW4, "a", -10_000),
+        (FeatureKey::UW4, "b", 10_000),
+    ]));
+    assert_eq!(overridden.options().min_chunk_chars, Some(2));
+    assert_eq!(overridden.parse("abcdeabcd"), vec!["abcdea", "bcd"]);
+}
@@ -0,0 +1,165 @@
+//! Model tests.
+
+use std::ptr;
+
+use budouy::model::{
+    CompiledModel, FeatureKey, InnerModel, ModelError, load_default_parsers, model_to_json,
+    parse_model_json,
+};
+use budouy::{Model, Parser};
+
+fn sample_model() -> Model {
+    let mut model = Model::new();
+    model.insert(
+        FeatureKey::UW4,
+        InnerModel::from([("a".to_string(), 100), ("b".to_string(), -40)]),
+    );
+    model.insert(
+        FeatureKey::TW1,
+        InnerModel::from([("abc".to_string(), 7), ("abcd".to_string(), 3)]),
+    );
+    model
+}
+
+#[test]
+fn compiled_model_looks_up_weights() {
+    let compiled = CompiledModel::new(&sample_model());
+    assert_eq!(compiled.weight(FeatureKey::UW4, "a"), 100);
+    assert_eq!(compiled.weight(FeatureKey::UW4, "b"), -40);
+    assert_eq!(compiled.weight(FeatureKey::UW4, "c"), 0);
+    assert_eq!(compiled.weight(FeatureKey::UW5, "a"), 0);
+    assert_eq!(compiled.weight(FeatureKey::TW1, "abc"), 7);
+    assert_eq!(compiled.len(), 3);
+    assert!((compiled.base_score() - -35.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn compiled_model_round_trips_all_entries() {
+    assert_eq!(
+        CompiledModel::new(&sample_model()).to_model(),
+        sample_model()
+    );
+}
+
+#[test]
+fn binary_model_round_trips() {
+    let compiled = CompiledModel::new(&sample_model());
+    let bytes = compiled.as_bytes().to_vec();
+    assert!(CompiledModel::is_binary(&bytes));
+
+    let loaded = CompiledModel::from_bytes(bytes).expect("load");
+    assert_eq!(loaded, compiled);
+    assert_eq!(loaded.weight(FeatureKey::UW4, "b"), -40);
+    assert!((loaded.base_score() - -35.0).abs() < f64::EPSILON);
+    assert_eq!(loaded.to_model(), sample_model());
+
+    let leaked: &'static [u8] = Vec::leak(compiled.as_bytes().to_vec());
+    let loaded = CompiledModel::from_static(leaked).expect("load");
+    assert_eq!(loaded.weight(FeatureKey::TW1, "abc"), 7);
+}
+
+#[test]
+fn binary_model_rejects_malformed_data() {
+    let bytes = CompiledModel::new(&sample_model()).as_bytes().to_vec();
+    assert!(matches!(
+        CompiledModel::from_bytes(b"{}".to_vec()),
+        Err(ModelError::InvalidBinary(_))
+    ));
+    assert!(matches!(
+        CompiledModel::from_bytes(bytes[..bytes.len() - 1].to_vec()),
+        Err(ModelError::InvalidBinary(_))
+    ));
+
+    let mut future = bytes.clone();
+    future[4] = 2;
+    assert!(matches!(
+        CompiledModel::from_bytes(future),
+        Err(ModelError::UnsupportedVersion(2))
+    ));
+
+    // Swap the two UW4 entries so they are no longer sorted.
+    let mut unsorted = bytes;
+    let keys = 88;
+    let (first, second) = unsorted[keys..keys + 16].split_at_mut(8);
+    first.swap_with_slice(second);
+    assert!(matches!(
+        CompiledModel::from_bytes(unsorted),
+        Err(ModelError::InvalidBinary(_))
+    ));
+}
+
+#[test]
+fn parsers_share_compiled_model() {
+    let compiled = CompiledModel::new(&sample_model());
+    let first = Parser::from_compiled(compiled.clone());
+    let second = first.clone();
+    assert!(ptr::eq(
+        first.compiled_model().as_bytes(),
+        compiled.as_bytes()
+    ));
+    assert!(ptr::eq(
+        second.compiled_model().as_bytes(),
+        compiled.as_bytes()
+    ));
+    assert_eq!(
+        first.parse("xaxbx"),
+        Parser::new(sample_model()).parse("xaxbx")
+    );
+}
+
+#[test]
+fn model_json_round_trips() {
+    let json = model_to_json(&sample_model()).expect("serialize");
+    assert_eq!(
+        json,
+        r#"{"TW1":{"abc":7,"abcd":3},"UW4":{"a":100,"b":-40}}"#
+    );
+    assert_eq!(parse_model_json(&json).expect("parse"), sample_model());
+}
+
+#[cfg(feature = "vendored-models")]
+#[test]
+fn vendored_models_match_their_json() {
+    use budouy::model::{
+        load_default_japanese_parser, load_default_simplified_chinese_parser,
+        load_default_thai_parser, load_default_traditional_chinese_parser,
+    };
+
+    let cases = [
+        (
+            load_default_japanese_parser(),
+            include_str!("../src/models/ja.json"),
+        ),
+        (
+            load_default_simplified_chinese_parser(),
+            include_str!("../src/models/zh-hans.json"),
+        ),
+        (
+            load_default_traditional_chinese_parser(),
+            include_str!("../src/models/zh-hant.json"),
+        ),
+        (
+            load_default_thai_parser(),
+            include_str!("../src/models/th.json"),
+        ),
+    ];
+    for (parser, json) in cases {
+        let model = parse_model_json(json).expect("parse");
+        assert_eq!(parser.compiled_model(), &CompiledModel::new(&model));
+    }
+}
+
+#[test]
+fn default_parsers_follow_model_features() {
+    let parsers = load_default_parsers();
+    assert_eq!(parsers.contains_key("ja"), cfg!(feature = "model-ja"));
+    assert_eq!(
+        parsers.contains_key("zh-hans"),
+        cfg!(feature = "model-zh-hans")
+    );
+    assert_eq!(
+        parsers.contains_key("zh-hant"),
+        cfg!(feature = "model-zh-hant")
+    );
+    assert_eq!(parsers.contains_key("th"), cfg!(feature = "model-th"));
+}
@@ -1,7 +1,7 @@
 //! Parser tests.
 
 use budouy::model::{FeatureKey, InnerModel};
-use budouy::{Model, Parser};
+use budouy::{Model, Parser, ParserOptions};
 
 #[test]
 fn parse_separates_on_strong_feature() {
@@ -31,3 +31,160 @@ fn parse_empty_returns_empty_vec() {
     let result = parser.parse("");
     assert!(result.is_empty());
 }
+
+#[cfg(feature = "model-ja")]
+#[test]
+fn parse_with_default_japanese_model() {
+    use budouy::model::load_default_japanese_parser;
+
+    let parser = load_default_japanese_parser();
+    let result = parser.parse("Google の使命は、世界中の情報を整理し、世界中の人がアクセスできて使えるようにすることです。");
+    assert_eq!(
+        result,
+        vec![
+            "Google の",
+            "使命は、",
+            "世界中の",
+            "情報を",
+            "整理し、",
+            "世界中の",
+            "人が",
+            "アクセスできて",
+            "使えるように",
+            "する",
+            "ことです。",
+        ]
+    );
+}
+
+#[test]
+fn parse_spans_use_byte_offsets() {
+    let mut model: Model = Model::new();
+    let mut inner = InnerModel::new();
+    inner.insert("あ".to_string(), 10_000);
+    model.insert(FeatureKey::UW4, inner);
+    let parser = Parser::new(model);
+    let sentence = "xあyyあz";
+    assert_eq!(parser.parse_boundaries(sentence), vec![1, 4]);
+    assert_eq!(parser.parse_boundaries_bytes(sentence), vec![1, 6]);
+    assert_eq!(parser.parse_spans(sentence), vec![0..1, 1..6, 6..10]);
+    assert_eq!(
+        parser.parse_str(sentence).collect::<Vec<_>>(),
+        parser.parse(sentence)
+    );
+}
+
+#[test]
+fn parse_spans_empty_returns_empty_vec() {
+    let parser = Parser::new(Model::new());
+    assert!(parser.parse_spans("").is_empty());
+    assert_eq!(parser.parse_str("").count(), 0);
+}
+
+#[test]
+fn explain_matches_parse_boundaries() {
+    let mut model: Model = Model::new();
+    model.insert(
+        FeatureKey::UW4,
+        InnerModel::from([("b".to_string(), 10_000)]),
+    );
+    model.insert(FeatureKey::BW2, InnerModel::from([("ab".to_string(), -3)]));
+    let parser = Parser::new(model);
+    let explanations = parser.explain("abcab");
+
+    assert_eq!(explanations.len(), 4);
+    let breaks: Vec<usize> = explanations
+        .iter()
+        .filter(|explanation| explanation.is_boundary)
+        .map(|explanation| explanation.index)
+        .collect();
+    assert_eq!(breaks, parser.parse_boundaries("abcab"));
+
+    let first = &explanations[0];
+    assert!((first.base_score - -4998.5).abs() < f64::EPSILON);
+    assert!((first.score - 4998.5).abs() < f64::EPSILON);
+    let matched: Vec<(FeatureKey, &str, i32)> = first
+        .contributions
+        .iter()
+        .filter(|contribution| contribution.weight != 0)
+        .map(|contribution| {
+            (
+                contribution.feature,
+                contribution.ngram.as_str(),
+                contribution.weight,
+            )
+        })
+        .collect();
+    assert_eq!(
+        matched,
+        vec![(FeatureKey::UW4, "b", 10_000), (FeatureKey::BW2, "ab", -3)]
+    );
+}
+
+#[test]
+fn threshold_controls_break_density() {
+    let mut model: Model = Model::new();
+    model.insert(
+        FeatureKey::UW4,
+        InnerModel::from([("b".to_string(), 100), ("c".to_string(), 40)]),
+    );
+    // base score is -70, so "b" scores +30 and "c" scores -30.
+    let parser = Parser::new(model);
+    assert_eq!(parser.parse("abcabc"), vec!["a", "bca", "bc"]);
+
+    let mut options = ParserOptions::default();
+    options.threshold = 50.0;
+    assert_eq!(
+        parser.clone().with_options(options).parse("abcabc"),
+        vec!["abcabc"]
+    );
+
+    let mut options = ParserOptions::default();
+    options.threshold = -50.0;
+    assert_eq!(
+        parser.with_options(options).parse("abcabc"),
+        vec!["a", "b", "ca", "b", "c"]
+    );
+}
+
+#[test]
+fn min_chunk_chars_merges_short_chunks() {
+    let mut model: Model = Model::new();
+    model.insert(
+        FeatureKey::UW4,
+        InnerModel::from([("b".to_string(), 10_000)]),
+    );
+    let mut options = ParserOptions::default();
+    options.min_chunk_chars = Some(2);
+    let parser = Parser::new(model).with_options(options);
+    assert_eq!(parser.parse("abcdeabcd"), vec!["abcdea", "bcd"]);
+    assert_eq!(parser.parse("abcdeab"), vec!["abcdeab"]);
+    assert_eq!(parser.parse_boundaries("abcdeabcd"), vec![6]);
+}
+
+#[test]
+fn max_chunk_chars_forces_best_scoring_break() {
+    let mut model: Model = Model::new();
+    model.insert(
+        FeatureKey::UW4,
+        InnerModel::from([
+            ("c".to_string(), 10),
+            ("d".to_string(), 5),
+            ("x".to_string(), 40),
+        ]),
+    );
+    // base score is -27.5, so no position clears the default threshold.
+    let parser = Parser::new(model);
+    assert_eq!(parser.parse("abcdabcd"), vec!["abcdabcd"]);
+
+    let mut options = ParserOptions::default();
+    options.max_chunk_chars = Some(3);
+    let parser = parser.with_options(options);
+    assert_eq!(parser.parse("abcdabcd"), vec!["ab", "c", "dab", "cd"]);
+
+    let mut options = ParserOptions::default();
+    options.min_chunk_chars = Some(2);
+    options.max_chunk_chars = Some(3);
+    let parser = parser.with_options(options);
+    assert_eq!(parser.parse("abcdabcd"), vec!["ab", "cda", "bcd"]);
+}
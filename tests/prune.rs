@@ -0,0 +1,127 @@
+//! Model pruning and quantization tests.
+
+use budouy::eval::compare;
+use budouy::model::{
+    BASE_SCORE_NGRAM, FeatureKey, PruneOptions, Quantization, prune_model, quantize_model,
+};
+use budouy::{Model, Parser};
+
+pub mod common;
+
+use common::model;
+
+fn sample() -> Model {
+    model(&[
+        (FeatureKey::UW4, "a", 1000),
+        (FeatureKey::UW4, "b", -300),
+        (FeatureKey::UW4, "c", 300),
+        (FeatureKey::UW4, "d", 5),
+        (FeatureKey::BW2, "ab", -2),
+        (FeatureKey::BW2, "cd", 0),
+    ])
+}
+
+#[test]
+fn prune_drops_small_weights_and_keeps_top_k() {
+    let mut options = PruneOptions::default();
+    assert_eq!(
+        prune_model(&sample(), &options),
+        model(&[
+            (FeatureKey::UW4, "a", 1000),
+            (FeatureKey::UW4, "b", -300),
+            (FeatureKey::UW4, "c", 300),
+            (FeatureKey::UW4, "d", 5),
+            (FeatureKey::BW2, "ab", -2),
+        ])
+    );
+
+    // The dropped weights are kept for the base score.
+    options.min_weight = Some(5);
+    assert_eq!(
+        prune_model(&sample(), &options),
+        model(&[
+            (FeatureKey::UW1, BASE_SCORE_NGRAM, -2),
+            (FeatureKey::UW4, "a", 1000),
+            (FeatureKey::UW4, "b", -300),
+            (FeatureKey::UW4, "c", 300),
+            (FeatureKey::UW4, "d", 5),
+        ])
+    );
+
+    options.min_weight = None;
+    options.top_k = Some(2);
+    assert_eq!(
+        prune_model(&sample(), &options),
+        model(&[
+            (FeatureKey::UW1, BASE_SCORE_NGRAM, 305),
+            (FeatureKey::UW4, "a", 1000),
+            (FeatureKey::UW4, "b", -300),
+            (FeatureKey::BW2, "ab", -2),
+        ])
+    );
+}
+
+#[cfg(feature = "model-ja")]
+#[test]
+fn pruned_japanese_model_agrees_with_original() {
+    use budouy::model::{lint, load_default_japanese_parser};
+
+    let reference = load_default_japanese_parser();
+    let original = reference.compiled_model().to_model();
+    let corpus = "Google の使命は、世界中の情報を整理し、世界中の人がアクセスできて使えるようにすることです。\n\
+                  今日は良い天気ですね。明日も晴れるでしょうか。\n\
+                  吾輩は猫である。名前はまだ無い。どこで生れたかとんと見当がつかぬ。\n\
+                  東京都の天気予報によると、週末は雨が降る可能性が高いそうです。\n";
+    let mut options = PruneOptions::default();
+    options.min_weight = Some(500);
+    let pruned = prune_model(&original, &options);
+    assert!(lint(&pruned).is_empty());
+    let pruned = Parser::new(pruned);
+    assert!(compare(&reference, &pruned, corpus).metrics.accuracy > 0.95);
+
+    options.min_weight = None;
+    options.top_k = Some(50);
+    let pruned = Parser::new(prune_model(&original, &options));
+    assert!(compare(&reference, &pruned, corpus).metrics.accuracy > 0.95);
+}
+
+#[test]
+fn quantize_scales_weights_into_range() {
+    let quantized = quantize_model(&sample(), Quantization::I8);
+    assert_eq!(quantized.scale, 8);
+    assert_eq!(
+        quantized.model,
+        model(&[
+            (FeatureKey::UW4, "a", 125),
+            (FeatureKey::UW4, "b", -38),
+            (FeatureKey::UW4, "c", 38),
+            (FeatureKey::UW4, "d", 1),
+        ])
+    );
+
+    let quantized = quantize_model(&sample(), Quantization::I16);
+    assert_eq!(quantized.scale, 1);
+    assert_eq!(
+        quantized.model,
+        prune_model(&sample(), &PruneOptions::default())
+    );
+}
+
+#[test]
+fn compare_reports_agreement_with_reference() {
+    let reference = Parser::new(sample());
+    let quantized = Parser::new(quantize_model(&sample(), Quantization::I8).model);
+    let evaluation = compare(&reference, &quantized, "abcdabcd\n\ndcba\n");
+    assert_eq!(evaluation.metrics.sentences, 2);
+    assert!((evaluation.metrics.accuracy - 1.0).abs() < 1e-9);
+    assert!(evaluation.diffs.is_empty());
+
+    let other = Parser::new(model(&[(FeatureKey::UW4, "b", 1000)]));
+    let evaluation = compare(&reference, &other, "abcdabcd\n");
+    assert_eq!(evaluation.metrics.true_positives, 0);
+    assert_eq!(evaluation.metrics.false_positives, 2);
+    assert_eq!(evaluation.metrics.false_negatives, 1);
+    assert_eq!(evaluation.diffs.len(), 1);
+    assert_eq!(evaluation.diffs[0].gold, vec![4]);
+    assert_eq!(evaluation.diffs[0].predicted, vec![1, 5]);
+}
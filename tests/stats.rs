@@ -0,0 +1,87 @@
+//! Model statistics tests.
+
+use budouy::Model;
+use budouy::model::{FeatureKey, Script, model_stats};
+
+pub mod common;
+
+use common::model;
+
+#[test]
+fn stats_summarize_weights_per_feature() {
+    let model = model(&[
+        (FeatureKey::UW3, "は", 400),
+        (FeatureKey::UW4, "天", -100),
+        (FeatureKey::UW4, "気", -300),
+        (FeatureKey::UW4, "ア", 50),
+        (FeatureKey::UW5, "天", 10),
+        (FeatureKey::BW2, "天気", -60),
+    ]);
+    let stats = model_stats(&model, 2);
+    assert_eq!(stats.entries, 6);
+    assert!((stats.base_score - 0.0).abs() < f64::EPSILON);
+
+    let weights = stats.weights.expect("model has weights");
+    assert_eq!((weights.min, weights.max), (-300, 400));
+    assert_eq!((weights.q1, weights.median, weights.q3), (-100, -60, 10));
+    assert_eq!((weights.positive, weights.negative), (3, 3));
+    assert!(weights.mean.abs() < f64::EPSILON);
+
+    assert_eq!(stats.features.len(), FeatureKey::ALL.len());
+    let uw4 = &stats.features[3];
+    assert_eq!(uw4.feature, FeatureKey::UW4);
+    assert_eq!(uw4.entries, 3);
+    assert_eq!(
+        uw4.weights.as_ref().map(|weights| weights.median),
+        Some(-100)
+    );
+    assert!(stats.features[0].weights.is_none());
+
+    let positive: Vec<(&str, i32)> = stats
+        .strongest_positive
+        .iter()
+        .map(|entry| (entry.ngram.as_str(), entry.weight))
+        .collect();
+    assert_eq!(positive, vec![("は", 400), ("ア", 50)]);
+    let negative: Vec<(&str, i32)> = stats
+        .strongest_negative
+        .iter()
+        .map(|entry| (entry.ngram.as_str(), entry.weight))
+        .collect();
+    assert_eq!(negative, vec![("気", -300), ("天", -100)]);
+
+    let scripts: Vec<(Script, usize)> = stats
+        .scripts
+        .iter()
+        .map(|coverage| (coverage.script, coverage.characters))
+        .collect();
+    assert_eq!(
+        scripts,
+        vec![
+            (Script::Han, 2),
+            (Script::Hiragana, 1),
+            (Script::Katakana, 1)
+        ]
+    );
+}
+
+#[test]
+fn stats_of_empty_model() {
+    let stats = model_stats(&Model::new(), 10);
+    assert_eq!(stats.entries, 0);
+    assert!(stats.weights.is_none());
+    assert!(stats.strongest_positive.is_empty());
+    assert!(stats.scripts.is_empty());
+}
+
+#[test]
+fn script_classifies_characters() {
+    assert_eq!(Script::of('あ'), Script::Hiragana);
+    assert_eq!(Script::of('ｱ'), Script::Katakana);
+    assert_eq!(Script::of('々'), Script::Han);
+    assert_eq!(Script::of('ก'), Script::Thai);
+    assert_eq!(Script::of('Ｚ'), Script::Latin);
+    assert_eq!(Script::of('７'), Script::Digit);
+    assert_eq!(Script::of('。'), Script::Punctuation);
+    assert_eq!(Script::of('한'), Script::Hangul);
+}
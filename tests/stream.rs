@@ -0,0 +1,145 @@
+//! Streaming segmentation tests.
+
+use std::io::{self, ErrorKind, Read};
+
+use budouy::dictionary::{Dictionary, Rule};
+use budouy::model::FeatureKey;
+use budouy::{Model, Parser, ParserOptions};
+
+const TEXT: &str = "abcdeabcdbbbaaacdxyzabcab\nbacdaabcbcbcdddeabcbd";
+
+fn parser() -> Parser {
+    let mut model = Model::new();
+    model.insert(
+        FeatureKey::UW4,
+        [("a".to_string(), 3000), ("b".to_string(), 400)].into(),
+    );
+    model.insert(
+        FeatureKey::BW2,
+        [("cd".to_string(), 1500), ("ab".to_string(), -2000)].into(),
+    );
+    model.insert(FeatureKey::TW4, [("bcd".to_string(), 900)].into());
+    Parser::new(model)
+}
+
+fn variants() -> Vec<Parser> {
+    let mut dictionary = Dictionary::new();
+    dictionary.insert("bcdb", Rule::Keep);
+    dictionary.insert("xyz", Rule::Break);
+    dictionary.insert("aa", Rule::Break);
+
+    let mut variants = vec![parser(), parser().with_dictionary(dictionary.clone())];
+    for (min, max) in [
+        (Some(2), None),
+        (None, Some(1)),
+        (None, Some(4)),
+        (Some(3), Some(5)),
+    ] {
+        let mut options = ParserOptions::default();
+        options.min_chunk_chars = min;
+        options.max_chunk_chars = max;
+        variants.push(parser().with_options(options.clone()));
+        variants.push(
+            parser()
+                .with_options(options.clone())
+                .with_dictionary(Dictionary::new()),
+        );
+        variants.push(
+            parser()
+                .with_options(options)
+                .with_dictionary(dictionary.clone()),
+        );
+    }
+    variants
+}
+
+/// Split `text` into pieces at pseudo-random `char` boundaries.
+fn split(text: &str, seed: u64) -> Vec<&str> {
+    let mut state = seed;
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        let chars = usize::try_from(state >> 61).unwrap_or(0);
+        let end = rest
+            .char_indices()
+            .nth(chars)
+            .map_or(rest.len(), |(index, _)| index);
+        pieces.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    pieces
+}
+
+#[test]
+fn streaming_matches_whole_text_parsing() {
+    for parser in variants() {
+        let expected = parser.parse(TEXT);
+        for seed in 0..20 {
+            let pieces = split(TEXT, seed);
+            let chunks: Vec<String> = parser.parse_iter(&pieces).collect();
+            assert_eq!(chunks, expected, "pieces: {pieces:?}");
+        }
+        let chars: Vec<String> = TEXT.chars().map(String::from).collect();
+        assert_eq!(parser.parse_iter(chars).collect::<Vec<_>>(), expected);
+    }
+}
+
+#[test]
+fn segmenter_returns_chunks_incrementally() {
+    let parser = parser();
+    let mut segmenter = parser.segmenter();
+    assert!(segmenter.push("").is_empty());
+    assert!(segmenter.push("abc").is_empty());
+    assert_eq!(segmenter.push("deabcdxyz"), vec!["abcde"]);
+    assert_eq!(segmenter.finish(), vec!["abcdxyz"]);
+    assert!(segmenter.finish().is_empty());
+    assert!(parser.parse_iter([""; 3]).next().is_none());
+}
+
+#[cfg(feature = "model-ja")]
+#[test]
+fn streaming_matches_default_model() {
+    use budouy::model::load_default_japanese_parser;
+
+    let parser = load_default_japanese_parser();
+    let text = "今日は良い天気ですね。\n明日は雨が降るらしいので、傘を持って出かけましょう。";
+    let expected = parser.parse(text);
+    for seed in 0..10 {
+        let chunks: Vec<String> = parser.parse_iter(split(text, seed)).collect();
+        assert_eq!(chunks, expected);
+    }
+}
+
+/// Reader that returns at most three bytes per read.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.0.len().min(buf.len()).min(3);
+        buf[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn reader_decodes_split_characters() {
+    let parser = parser()
+        .with_dictionary(Dictionary::parse_tsv("bcdb\nβγ\tbreak").expect("valid dictionary"));
+    let text = format!("{TEXT}αβγabcdé");
+    let chunks: Vec<String> = parser
+        .parse_reader(Trickle(text.as_bytes()))
+        .collect::<io::Result<_>>()
+        .expect("valid UTF-8");
+    assert_eq!(chunks, parser.parse(&text));
+
+    let mut invalid = parser.parse_reader(Trickle(b"abc\xE3\x81"));
+    let error = invalid
+        .find_map(Result::err)
+        .expect("truncated UTF-8 is an error");
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(invalid.next().is_none());
+}
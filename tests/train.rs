@@ -0,0 +1,42 @@
+//! Training tests.
+
+#![cfg(feature = "train")]
+
+use budouy::Parser;
+use budouy::model::{model_to_json, parse_model_json};
+use budouy::train::{TrainOptions, train};
+
+const CORPUS: &str = "\
+abc▁def▁gh
+de▁abc▁fgh
+gh▁def▁abc
+";
+
+#[test]
+fn trained_model_reproduces_corpus() {
+    let mut options = TrainOptions::default();
+    options.iterations = 200;
+    let model = train(CORPUS, Some(options));
+    let parser =
+        Parser::new(parse_model_json(&model_to_json(&model).expect("serialize")).expect("parse"));
+
+    for line in CORPUS.lines() {
+        let sentence = line.replace('▁', "");
+        assert_eq!(parser.parse(&sentence).join("▁"), line);
+    }
+}
+
+#[test]
+fn train_honors_custom_separator() {
+    let mut options = TrainOptions::default();
+    options.iterations = 50;
+    options.separator = '|';
+    let model = train("ab|cd\ncd|ab\n", Some(options));
+    let parser = Parser::new(model);
+    assert_eq!(parser.parse("abcd"), vec!["ab", "cd"]);
+}
+
+#[test]
+fn train_empty_corpus_returns_empty_model() {
+    assert!(train("", None).is_empty());
+}
@@ -0,0 +1,64 @@
+//! Line wrapping tests.
+
+#![cfg(feature = "wrap")]
+
+use budouy::wrap::{display_width, wrap};
+use budouy::{Model, Parser};
+
+pub mod common;
+
+use common::parser_breaking_before;
+
+#[test]
+fn display_width_counts_full_width_as_two() {
+    assert_eq!(display_width("abc"), 3);
+    assert_eq!(display_width("あいう"), 6);
+    assert_eq!(display_width("ＡＢ"), 4);
+}
+
+#[test]
+fn wrap_breaks_only_at_boundaries() {
+    let parser = parser_breaking_before("あ");
+    assert_eq!(wrap(&parser, "xxあyyあzzz", 6), vec!["xxあyy", "あzzz"]);
+    assert_eq!(wrap(&parser, "xxあyyあzzz", 20), vec!["xxあyyあzzz"]);
+}
+
+#[test]
+fn wrap_falls_back_to_graphemes_for_long_chunks() {
+    let parser = Parser::new(Model::new());
+    assert_eq!(
+        wrap(&parser, "いろはにほへと", 5),
+        vec!["いろ", "はに", "ほへ", "と"]
+    );
+    assert_eq!(
+        wrap(&parser, "e\u{301}e\u{301}e\u{301}", 2),
+        vec!["e\u{301}e\u{301}", "e\u{301}"]
+    );
+}
+
+#[test]
+fn wrap_trims_whitespace_at_breaks_and_keeps_newlines() {
+    let parser = parser_breaking_before("b");
+    assert_eq!(
+        wrap(&parser, "aaa bbb\n\naaa", 4),
+        vec!["aaa", "bbb", "", "aaa"]
+    );
+}
+
+#[test]
+fn wrap_drops_whitespace_at_breaks_inside_long_chunks() {
+    let parser = Parser::new(Model::new());
+    assert_eq!(wrap(&parser, "ああ  xあ", 2), vec!["あ", "あ", "x", "あ"]);
+    assert_eq!(wrap(&parser, "ああ xx", 2), vec!["あ", "あ", "xx"]);
+    assert_eq!(wrap(&parser, "     xx", 2), vec!["xx"]);
+    assert_eq!(wrap(&parser, "  \n", 2), vec![""]);
+}
+
+#[test]
+fn wrap_keeps_paragraph_indentation() {
+    let parser = parser_breaking_before("b");
+    assert_eq!(
+        wrap(&parser, "  aaa bbb\n   aaa", 6),
+        vec!["  aaa", "bbb", "   aaa"]
+    );
+}